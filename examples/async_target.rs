@@ -0,0 +1,35 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+use loggerithm::{logger, log, LogContext};
+use loggerithm::logger::Logger;
+use loggerithm::level::INFO;
+use loggerithm::target::{Async, Overflow};
+
+logger!(Logger::new()
+    .with_target(Async::options()
+        .set_capacity(4)
+        .set_overflow(Overflow::DropNewest)
+        .wrap(|context : &LogContext| {
+            // A slow target, such as a network connection.
+            thread::sleep(Duration::from_millis(20));
+            println!("{} {}", context.level_name_p(), context.message());
+        })
+        .unwrap()
+    )
+);
+
+fn main() {
+    let start = Instant::now();
+    for index in 0..10 {
+        log!(INFO, "Message {}.", index);
+    }
+    println!("Logging 10 messages took {:?}.", start.elapsed());
+
+    // Give the background thread time to write the queued messages.
+    thread::sleep(Duration::from_millis(200));
+    log!(INFO, "The number of dropped messages is written before this message.");
+
+    // Write the last message before the program exits.
+    loggerithm::shutdown();
+}
@@ -0,0 +1,24 @@
+use std::thread;
+use std::time::Duration;
+
+use loggerithm::{logger, log};
+use loggerithm::logger::Logger;
+use loggerithm::level::{TRACE, INFO};
+use loggerithm::target::{Compressed, Compression};
+
+logger!(Logger::new()
+    .with_target(Compressed::options()
+        .set_compression(Compression::Gzip)
+        .open("logs/compressed_target.log.gz")
+        .expect("Failed to open log file")
+    )
+);
+
+fn main() {
+    for i in 0..1000 {
+        log!(TRACE, "Verbose message number {}.", i);
+    }
+    log!(INFO, "Read this file with `zcat logs/compressed_target.log.gz`.");
+    // The stream is finished once no messages have been logged for a second.
+    thread::sleep(Duration::from_millis(1500));
+}
@@ -0,0 +1,41 @@
+use loggerithm::{logger, log};
+use loggerithm::level::{DEBUG, INFO};
+use loggerithm::config::Config;
+
+logger!(super);
+
+mod db {
+    use loggerithm::{logger, log};
+    use loggerithm::level::DEBUG;
+
+    logger!(super);
+
+    pub fn query() {
+        log!(DEBUG, "Running a query. This is only written to `logs/config_file.jsonl`.");
+    }
+}
+
+// Usually this is in a file, loaded with `loggerithm::config::load`.
+const CONFIG : &str = r#"
+[severities]
+"config_file::db" = "debug"
+
+[loggers."config_file"]
+min_severity = "info"
+targets = [
+    { type = "stdout", format = { pattern = "{level:p} {module:p} {message}" }, min_severity = "info" },
+    { type = "file", path = "logs/config_file.jsonl", format = "json" }
+]
+"#;
+
+fn main() {
+    Config::from_toml(CONFIG).unwrap().apply().unwrap();
+    log!(INFO, "This is written to stdout and `logs/config_file.jsonl`.");
+    log!(DEBUG, "This is not logged.");
+    db::query();
+
+    let error = Config::from_toml("[loggers.\"config_file\"]\nmin_severity = \"loud\"").unwrap().apply().unwrap_err();
+    log!(INFO, "{}", error);
+    let error = Config::from_toml("[loggers.\"config_file::typo\"]").unwrap().apply().unwrap_err();
+    log!(INFO, "{}", error);
+}
@@ -5,7 +5,7 @@ use loggerithm::level::{LogLevel, INFO};
 
 logger!(super);
 log_level!(MY_AMAZING_CUSTOM_LOGGING_LEVEL, LogLevel::new(30)
-    .formatted(|v| v.magenta().on_white().reverse())
+    .formatted(|v| v.magenta().on_white().reversed())
 );
 
 fn main() {
@@ -0,0 +1,52 @@
+#![allow(unused_parens, clippy::needless_return)]
+
+use std::sync::Mutex;
+
+use loggerithm::{logger, log, LogContext};
+use loggerithm::logger::Logger;
+use loggerithm::level::{DEBUG, INFO, WARN};
+use loggerithm::target::{Target, TargetError};
+
+/// A target that keeps a limited number
+/// of messages, and prints them when it is
+/// flushed.
+struct Batch {
+    limit : usize,
+    lines : Mutex<Vec<String>>
+}
+impl Target for Batch {
+    fn write(&self, context : &LogContext) -> Result<(), TargetError> {
+        let mut lines = self.lines.lock().unwrap();
+        if (lines.len() >= self.limit) {
+            return Err(TargetError::Other(format!("The batch is full, so `{}` was lost.", context.message())));
+        }
+        lines.push(format!("{} {}", context.level_name_p(), context.message()));
+        return Ok(());
+    }
+    fn flush(&self) -> Result<(), TargetError> {
+        for line in self.lines.lock().unwrap().drain(..) {
+            println!("{}", line);
+        }
+        return Ok(());
+    }
+    fn enabled(&self, context : &LogContext) -> bool {
+        // Debug messages are not worth keeping.
+        return context.level().get_severity() > DEBUG::SEVERITY();
+    }
+}
+
+logger!(Logger::new()
+    .with_target(Batch {
+        limit : 2,
+        lines : Mutex::new(Vec::new())
+    })
+    .set_error_handler(|error : &TargetError| println!("Error: {}", error))
+);
+
+fn main() {
+    log!(DEBUG, "This is not given to the target.");
+    log!(INFO, "This is kept.");
+    log!(WARN, "This is kept too.");
+    log!(INFO, "This does not fit.");
+    loggerithm::flush();
+}
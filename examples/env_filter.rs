@@ -0,0 +1,27 @@
+use loggerithm::{logger, log, log_level};
+use loggerithm::level::{LogLevel, DEBUG, INFO};
+
+logger!(super);
+
+log_level!(VERBOSE, LogLevel::new(5));
+
+mod db {
+    use loggerithm::{logger, log};
+    use loggerithm::level::DEBUG;
+
+    logger!(super);
+
+    pub fn query() {
+        log!(super::VERBOSE, "Running a query.");
+        log!(DEBUG, "The query finished.");
+    }
+}
+
+fn main() {
+    loggerithm::env::apply("info,env_filter::db=verbose").unwrap();
+    // Run with `LOGGERITHM=debug,env_filter::db=info` to change what is logged.
+    loggerithm::env::init().unwrap();
+    log!(INFO, "Starting.");
+    log!(DEBUG, "This is only logged with `debug` or lower.");
+    db::query();
+}
@@ -0,0 +1,17 @@
+use loggerithm::{logger, log};
+use loggerithm::logger::Logger;
+use loggerithm::level::{INFO, WARN};
+use loggerithm::target::{File, SyncPolicy};
+
+logger!(Logger::new()
+    .with_target(File::options()
+        .set_sync_policy(SyncPolicy::OnFlush)
+        .open("logs/file_target.log")
+        .expect("Failed to open log file")
+    )
+);
+
+fn main() {
+    log!(INFO, "This message is written to `logs/file_target.log`.");
+    log!(WARN, "Parent directories are created if they are missing.");
+}
@@ -0,0 +1,39 @@
+use loggerithm::{logger, log, LogContext};
+use loggerithm::logger::Logger;
+use loggerithm::level::{TRACE, DEBUG, INFO, WARN};
+use loggerithm::target::{Filtered, Stdout, File};
+
+logger!(Logger::new()
+    .set_min_severity(TRACE::SEVERITY)
+    // Only `INFO` and above are printed.
+    .with_target(Filtered::new(Stdout::new())
+        .set_min_severity(INFO::SEVERITY)
+    )
+    // Everything is written to the file.
+    .with_target(File::open("logs/filtered_target.log")
+        .expect("Failed to open log file")
+    )
+    // Only messages from the `db` module are printed here.
+    .with_target(Filtered::new(|context : &LogContext| println!("[db] {}", context.message()))
+        .set_filter(|context : &LogContext| context.module().ends_with("::db"))
+    )
+);
+
+mod db {
+    use loggerithm::{logger, log};
+    use loggerithm::level::WARN;
+
+    logger!(super);
+
+    pub fn query() {
+        log!(WARN, "The query was slow.");
+    }
+}
+
+fn main() {
+    log!(TRACE, "This is only written to `logs/filtered_target.log`.");
+    log!(DEBUG, "So is this.");
+    log!(INFO, "This is printed, and written to the file.");
+    log!(WARN, "So is this.");
+    db::query();
+}
@@ -0,0 +1,56 @@
+use loggerithm::{logger, log, LogContext};
+use loggerithm::logger::Logger;
+use loggerithm::level::{DEBUG, INFO, WARN, ERROR};
+use loggerithm::filter::Filter;
+use loggerithm::target::Filtered;
+
+logger!(Logger::new()
+    // Nothing from the `noisy` module is logged, unless it is an error.
+    .set_filter(Filter::module_prefix("filters::noisy").not()
+        .or(Filter::severity_between(ERROR::SEVERITY()..))
+    )
+    .with_target(Filtered::new(|context : &LogContext| println!("[all]      {}", context.message())))
+    // Warnings, and anything from the `db` module, are also sent here.
+    .with_target(Filtered::new(|context : &LogContext| println!("[alerts]   {}", context.message()))
+        .set_filter(Filter::severity_between(WARN::SEVERITY()..)
+            .or(Filter::module_prefix("filters::db"))
+        )
+    )
+    // Closures can be combined with filters too.
+    .with_target(Filtered::new(|context : &LogContext| println!("[requests] {}", context.message()))
+        .set_filter(Filter::message_contains("request")
+            .and(|context : &LogContext| context.level().get_severity() < WARN::SEVERITY())
+        )
+    )
+);
+
+mod db {
+    use loggerithm::{logger, log};
+    use loggerithm::level::DEBUG;
+
+    logger!(super);
+
+    pub fn query() {
+        log!(DEBUG, "Running a query.");
+    }
+}
+
+mod noisy {
+    use loggerithm::{logger, log};
+    use loggerithm::level::{INFO, ERROR};
+
+    logger!(super);
+
+    pub fn chatter() {
+        log!(INFO, "This is not logged.");
+        log!(ERROR, "This error is logged.");
+    }
+}
+
+fn main() {
+    log!(INFO, "Handling a request.");
+    log!(WARN, "The request was slow.");
+    log!(DEBUG, "Nothing special.");
+    db::query();
+    noisy::chatter();
+}
@@ -0,0 +1,35 @@
+use std::fs;
+use std::thread;
+use std::time::Duration;
+
+use loggerithm::{logger, log};
+use loggerithm::logger::Logger;
+use loggerithm::level::{DEBUG, INFO};
+use loggerithm::format::Logfmt;
+use loggerithm::target::Stdout;
+
+logger!(super);
+
+fn main() {
+    log!(INFO, "Logged by the default logger.");
+
+    // Replace a logger from code.
+    loggerithm::reload([("hot_reload", Logger::new()
+        .with_target(Stdout::new().set_formatter(Logfmt::new()))
+    )]);
+    log!(DEBUG, "Logged by the new logger.");
+
+    // Replace a logger whenever a file changes.
+    let path = std::env::temp_dir().join("loggerithm_hot_reload.toml");
+    fs::write(&path, "[loggers.\"hot_reload\"]\nmin_severity = \"info\"\ntargets = [{ type = \"stdout\" }]\n").unwrap();
+    let _watcher = loggerithm::config::watch(&path, Duration::from_millis(50)).unwrap();
+    log!(INFO, "Logged using the file.");
+    log!(DEBUG, "Not logged, as the file sets the minimum severity to `info`.");
+
+    thread::sleep(Duration::from_millis(100));
+    fs::write(&path, "[loggers.\"hot_reload\"]\nmin_severity = \"debug\"\ntargets = [{ type = \"stdout\", format = \"json\" }]\n").unwrap();
+    thread::sleep(Duration::from_millis(200));
+    log!(DEBUG, "Logged after the file changed.");
+
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,15 @@
+use loggerithm::{logger, log};
+use loggerithm::logger::Logger;
+use loggerithm::level::{INFO, WARN};
+use loggerithm::format::{Formatter, Json};
+
+logger!(Logger::new()
+    .add_target(|context| {
+        println!("{}", Json::new().format(context));
+    })
+);
+
+fn main() {
+    log!(INFO, "Each message is printed as a JSON object.");
+    log!(WARN, "Quotes (\"), backslashes (\\) and\nnewlines are escaped.");
+}
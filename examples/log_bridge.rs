@@ -0,0 +1,24 @@
+mod database {
+    use loggerithm::logger;
+    use loggerithm::logger::Logger;
+    use loggerithm::level::TRACE;
+
+    logger!(Logger::default()
+        .set_min_severity(TRACE::SEVERITY)
+    );
+
+    pub fn connect() {
+        log::trace!("Records from the `log` crate use the logger of the module they came from.");
+    }
+}
+
+use loggerithm::logger;
+
+logger!(super);
+
+fn main() {
+    loggerithm::bridge::log::init().expect("A logger was already set");
+    log::info!("This is logged through loggerithm.");
+    log::debug!("This is not logged because it is below the minimum severity.");
+    database::connect();
+}
@@ -0,0 +1,15 @@
+use loggerithm::{logger, log};
+use loggerithm::logger::Logger;
+use loggerithm::level::{INFO, WARN};
+use loggerithm::format::{Formatter, Logfmt};
+
+logger!(Logger::new()
+    .add_target(|context| {
+        println!("{}", Logfmt::new().format(context));
+    })
+);
+
+fn main() {
+    log!(INFO, "Each message is printed as key=value pairs.");
+    log!(WARN, "Quotes (\") and\nnewlines are escaped.");
+}
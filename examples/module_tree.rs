@@ -46,7 +46,9 @@ mod module_other {
         }
     }
 
+    #[allow(unused_imports)]
     use loggerithm::log;
+    #[allow(unused_imports)]
     use loggerithm::level::INFO;
 
     pub fn main() {
@@ -0,0 +1,18 @@
+use loggerithm::{logger, log};
+use loggerithm::logger::Logger;
+use loggerithm::level::{INFO, WARN};
+use loggerithm::format::Pattern;
+use loggerithm::target::Stdout;
+
+logger!(Logger::new()
+    .with_target(Stdout::new()
+        .set_formatter(Pattern::new("{time:%H:%M:%S} [{level:fp}] {module:p}:{line} {message:f}")
+            .expect("Invalid pattern")
+        )
+    )
+);
+
+fn main() {
+    log!(INFO, "The layout of this message comes from a pattern.");
+    log!(WARN, "Unknown placeholders are reported when the logger is created.");
+}
@@ -0,0 +1,21 @@
+use loggerithm::{logger, log};
+use loggerithm::logger::Logger;
+use loggerithm::level::INFO;
+use loggerithm::target::{RotatingFile, Rotation, Naming};
+
+logger!(Logger::new()
+    .with_target(RotatingFile::options()
+        .set_max_size(256)
+        .set_rotation(Rotation::Daily)
+        .set_naming(Naming::Numbered)
+        .set_max_archives(3)
+        .open("logs/rotating_file_target.log")
+        .expect("Failed to open log file")
+    )
+);
+
+fn main() {
+    for i in 0..20 {
+        log!(INFO, "Message number {}. Only the last 3 archives are kept.", i);
+    }
+}
@@ -0,0 +1,29 @@
+use loggerithm::{logger, log};
+use loggerithm::level::{DEBUG, INFO};
+
+logger!(super);
+
+mod db {
+    use loggerithm::{logger, log};
+    use loggerithm::level::DEBUG;
+
+    logger!(super);
+
+    pub fn query() {
+        log!(DEBUG, "Running a query.");
+    }
+}
+
+fn main() {
+    log!(INFO, "`DEBUG` messages are not logged by the default logger.");
+    db::query();
+
+    loggerithm::set_module_min_severity("runtime_severity::db", DEBUG::SEVERITY);
+    log!(INFO, "Now they are logged in the `db` module, but not here.");
+    db::query();
+    log!(DEBUG, "This is not logged.");
+
+    loggerithm::clear_module_min_severity("runtime_severity::db");
+    log!(INFO, "And now they are not logged again.");
+    db::query();
+}
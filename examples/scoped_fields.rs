@@ -0,0 +1,28 @@
+use loggerithm::{logger, log};
+use loggerithm::logger::Logger;
+use loggerithm::level::INFO;
+use loggerithm::format::Logfmt;
+use loggerithm::target::Stdout;
+
+logger!(Logger::new()
+    .with_target(Stdout::new()
+        .set_formatter(Logfmt::new())
+    )
+);
+
+fn handle(request_id : u64, user_id : u64) {
+    let _request = loggerithm::scope(("request_id", request_id));
+    log!(INFO, "Request started.");
+    {
+        let _user = loggerithm::scope([("user_id", user_id)]);
+        log!(INFO, "Every message in this block has a `user_id` too.");
+        log!(INFO, user_id = 0; "Fields passed to `log!` shadow scoped fields.");
+    }
+    log!(INFO, "Request finished.");
+}
+
+fn main() {
+    handle(1, 42);
+    handle(2, 7);
+    log!(INFO, "Scoped fields are gone once their guard is dropped.");
+}
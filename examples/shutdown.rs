@@ -0,0 +1,22 @@
+use loggerithm::{logger, log};
+use loggerithm::logger::Logger;
+use loggerithm::level::{INFO, FATAL};
+use loggerithm::target::{File, FlushPolicy};
+
+logger!(Logger::new()
+    .with_target(File::options()
+        .set_flush_policy(FlushPolicy::Buffered)
+        .open("logs/shutdown.log")
+        .expect("Failed to open log file")
+    )
+);
+
+fn main() {
+    // Buffered messages are flushed when `main` returns.
+    let _guard = loggerithm::shutdown_guard();
+
+    log!(INFO, "This message is buffered.");
+    loggerithm::flush();
+    log!(INFO, "This message is buffered until the guard is dropped.");
+    log!(FATAL, "`FATAL` messages are flushed straight away.");
+}
@@ -0,0 +1,24 @@
+use loggerithm::{logger, log, span};
+use loggerithm::logger::Logger;
+use loggerithm::level::{DEBUG, INFO};
+use loggerithm::format::Pattern;
+use loggerithm::target::Stdout;
+
+logger!(Logger::new()
+    .set_min_severity(DEBUG::SEVERITY)
+    .with_target(Stdout::new()
+        .set_formatter(Pattern::new("{level:pf} [ {span:p} ] {message}{fields}").unwrap())
+    )
+);
+
+fn parse(source : &str) {
+    let _span = span!(DEBUG, "parse");
+    log!(INFO, "Parsing {} lines.", source.lines().count());
+}
+
+fn main() {
+    log!(INFO, "Not in a span.");
+    let _span = span!(INFO, "load_config");
+    parse("a = 1\nb = 2\n");
+    log!(INFO, "Loaded.");
+}
@@ -0,0 +1,19 @@
+use loggerithm::{logger, log};
+use loggerithm::logger::Logger;
+use loggerithm::level::{INFO, WARN};
+use loggerithm::format::Json;
+use loggerithm::target::Stdout;
+
+logger!(Logger::new()
+    .with_target(Stdout::new())
+    .with_target(Stdout::new()
+        .set_formatter(Json::new())
+    )
+);
+
+fn main() {
+    let path = std::path::Path::new("/index.html");
+    log!(INFO, user_id = 42, path = ?path, cached = true; "Served {} bytes.", 1024);
+    let error = "connection reset";
+    log!(WARN, error = %error; "Fields are added before the `;`.");
+}
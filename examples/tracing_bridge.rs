@@ -0,0 +1,20 @@
+use tracing_subscriber::prelude::*;
+
+use loggerithm::logger;
+use loggerithm::logger::Logger;
+use loggerithm::bridge::tracing::TracingLayer;
+use loggerithm::target::Stdout;
+
+logger!(Logger::new()
+    .with_target(Stdout::new())
+);
+
+fn main() {
+    tracing_subscriber::registry()
+        .with(TracingLayer::new())
+        .init();
+    tracing::info!("Events from the `tracing` crate are logged through loggerithm.");
+    let span = tracing::info_span!("load_config");
+    let _enter = span.enter();
+    tracing::warn!(path = "config.toml", attempt = 2, "The name of the current span is included, and the fields are structured.");
+}
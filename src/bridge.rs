@@ -0,0 +1,20 @@
+//! Bridges that route messages from other
+//! logging libraries into loggerithm.
+//! 
+//! Each bridge is behind a feature with
+//! the same name as the library.
+//! 
+//! Both bridges treat the target of a
+//! message as its module. The target is
+//! the module path, unless it was set in
+//! the macro that logged the message. It
+//! is used to find the logger, to check
+//! the minimum severity, and as the
+//! module in the `LogContext`.
+
+
+
+#[cfg(feature = "log")]
+pub mod log;
+#[cfg(feature = "tracing")]
+pub mod tracing;
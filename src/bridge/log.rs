@@ -0,0 +1,87 @@
+//! A bridge from the `log` crate.
+//! 
+//! Requires the `log` feature.
+//! 
+//! # Examples
+//! 
+//! ```
+//! loggerithm::bridge::log::init().unwrap();
+//! log::info!("This is logged by the default logger.");
+//! ```
+
+
+
+use log::{Level, LevelFilter, Log, Metadata, Record, SetLoggerError};
+
+use crate::internal;
+use crate::level::{self, LogLevel};
+
+
+
+/// A `log::Log` implementation that gives
+/// each `log::Record` to the logger of
+/// the module that it came from.
+/// 
+/// The module is the record's target,
+/// which is its module path unless it was
+/// set in the `log` macro. If no logger is
+/// registered for the module, the closest
+/// parent module's logger is used.
+pub struct LogBridge;
+impl Log for LogBridge {
+    fn enabled(&self, metadata : &Metadata) -> bool {
+        let module = metadata.target();
+        return match (internal::find_module_logger(module, false)) {
+            Some(logger) => logger.enabled(level_of(metadata.level()), module),
+            None         => false
+        };
+    }
+    fn log(&self, record : &Record) {
+        let module = record.target();
+        if let Some(logger) = internal::find_module_logger(module, false) {
+            logger.log_args(
+                level_of(record.level()),
+                module, (record.line().unwrap_or(0), 0),
+                *record.args()
+            );
+        }
+    }
+    fn flush(&self) {
+        crate::flush();
+    }
+}
+
+
+
+/// Get the built-in level that a `log::Level`
+/// is logged at.
+/// 
+/// # Arguments
+/// 
+/// * `level` - The `log` level.
+/// 
+/// # Returns
+/// 
+/// `TRACE`, `DEBUG`, `INFO`, `WARN` or `ERROR`.
+pub fn level_of(level : Level) -> &'static LogLevel {
+    return match (level) {
+        Level::Trace => &level::TRACE::LEVEL,
+        Level::Debug => &level::DEBUG::LEVEL,
+        Level::Info  => &level::INFO::LEVEL,
+        Level::Warn  => &level::WARN::LEVEL,
+        Level::Error => &level::ERROR::LEVEL
+    };
+}
+
+/// Set `LogBridge` as the logger used by
+/// the `log` crate.
+/// 
+/// # Returns
+/// 
+/// An error if a logger has already been
+/// set.
+pub fn init() -> Result<(), SetLoggerError> {
+    log::set_logger(&LogBridge)?;
+    log::set_max_level(LevelFilter::Trace);
+    return Ok(());
+}
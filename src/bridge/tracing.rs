@@ -0,0 +1,148 @@
+//! A bridge from the `tracing` crate.
+//! 
+//! Requires the `tracing` feature.
+//! 
+//! # Examples
+//! 
+//! ```
+//! use tracing_subscriber::prelude::*;
+//! use loggerithm::bridge::tracing::TracingLayer;
+//! tracing_subscriber::registry()
+//!     .with(TracingLayer::new())
+//!     .init();
+//! tracing::info!(answer = 42, "This is logged by the default logger.");
+//! ```
+
+
+
+use std::fmt;
+
+use tracing::{Event, Level, Subscriber};
+use tracing::field::{Field, Visit};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::internal;
+use crate::field::Value;
+use crate::level::{self, LogLevel};
+
+
+
+/// A `tracing_subscriber::Layer` that gives
+/// each `tracing::Event` to the logger of the
+/// module that it came from.
+/// 
+/// The names of the event's spans are added
+/// to `LogContext::spans`, after any entered
+/// with `span!`. The event's other fields are
+/// added as structured fields.
+/// 
+/// The module is the event's target, which
+/// is its module path unless it was set in
+/// the `tracing` macro. If no logger is
+/// registered for the module, the closest
+/// parent module's logger is used.
+/// 
+/// Events are not filtered before they reach
+/// the layer, so that other layers still see
+/// them.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TracingLayer {}
+/// Initialisation.
+impl TracingLayer {
+    /// Create a new layer.
+    pub fn new() -> TracingLayer {
+        return TracingLayer {};
+    }
+}
+impl<S> Layer<S> for TracingLayer
+    where S : Subscriber + for<'l> LookupSpan<'l>
+{
+    fn on_event(&self, event : &Event, context : Context<S>) {
+        let metadata = event.metadata();
+        let module   = metadata.target();
+        let level    = level_of(metadata.level());
+        let Some(logger) = internal::find_module_logger(module, false) else {return};
+        if (! logger.enabled(level, module)) {
+            return;
+        }
+        let mut visitor = TracingVisitor {
+            message : String::new(),
+            fields  : Vec::new()
+        };
+        event.record(&mut visitor);
+        let mut log_context = logger.create_context(
+            level,
+            String::from(module), (metadata.line().unwrap_or(0), 0),
+            visitor.message
+        );
+        if let Some(scope) = context.event_scope(event) {
+            log_context.push_spans(scope.from_root().map(|span| String::from(span.name())));
+        }
+        for (key, value) in visitor.fields {
+            log_context.add_field(key, value);
+        }
+        logger.log(log_context);
+    }
+}
+
+
+
+/// Collects the message and fields
+/// of an event.
+struct TracingVisitor {
+    message : String,
+    fields  : Vec<(String, Value)>
+}
+impl TracingVisitor {
+    /// Store a field, or the message.
+    fn record(&mut self, field : &Field, value : Value) {
+        if (field.name() == "message") {
+            self.message.push_str(&value.to_string());
+        } else {
+            self.fields.push((String::from(field.name()), value));
+        }
+    }
+}
+impl Visit for TracingVisitor {
+    fn record_bool(&mut self, field : &Field, value : bool) {
+        self.record(field, Value::from(value));
+    }
+    fn record_i64(&mut self, field : &Field, value : i64) {
+        self.record(field, Value::from(value));
+    }
+    fn record_u64(&mut self, field : &Field, value : u64) {
+        self.record(field, Value::from(value));
+    }
+    fn record_f64(&mut self, field : &Field, value : f64) {
+        self.record(field, Value::from(value));
+    }
+    fn record_str(&mut self, field : &Field, value : &str) {
+        self.record(field, Value::from(value));
+    }
+    fn record_debug(&mut self, field : &Field, value : &dyn fmt::Debug) {
+        self.record(field, Value::debug(value));
+    }
+}
+
+
+
+/// Get the built-in level that a
+/// `tracing::Level` is logged at.
+/// 
+/// # Arguments
+/// 
+/// * `level` - The `tracing` level.
+/// 
+/// # Returns
+/// 
+/// `TRACE`, `DEBUG`, `INFO`, `WARN` or `ERROR`.
+pub fn level_of(level : &Level) -> &'static LogLevel {
+    return match (*level) {
+        Level::TRACE => &level::TRACE::LEVEL,
+        Level::DEBUG => &level::DEBUG::LEVEL,
+        Level::INFO  => &level::INFO::LEVEL,
+        Level::WARN  => &level::WARN::LEVEL,
+        Level::ERROR => &level::ERROR::LEVEL
+    };
+}
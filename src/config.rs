@@ -0,0 +1,634 @@
+//! Everything related to building loggers
+//! from a configuration file.
+//! 
+//! Requires the `config` feature.
+//! 
+//! # Format
+//! 
+//! ```
+//! # use loggerithm::config::Config;
+//! let config = Config::from_toml(r#"
+//! ## The minimum severity of modules and their
+//! ## submodules, the same as `set_module_min_severity`.
+//! [severities]
+//! "app::db" = "debug"
+//! 
+//! ## A logger for a module, replacing the logger
+//! ## registered with `logger!` until the section
+//! ## is removed. `""` is the root.
+//! [loggers."app"]
+//! min_severity = "info"
+//! targets = [
+//!     { type = "stdout", format = "logfmt", min_severity = "warn" },
+//!     { type = "file", path = "logs/app.jsonl", format = "json" },
+//!     { type = "rotating_file", path = "logs/app.log", rotation = "daily", max_archives = 7, format = { pattern = "{time} {level:p} {message}" } }
+//! ]
+//! "#);
+//! assert!(config.is_ok());
+//! ```
+//! 
+//! Levels are the name of any registered
+//! level, ignoring case, a severity number,
+//! or `"off"`. Modules must have a logger
+//! registered with `logger!`. Loggers without
+//! a `min_severity` log everything.
+//! 
+//! The same structure can be written in JSON.
+//! 
+//! # Targets
+//! 
+//! Every target has an optional `format`,
+//! which is `"plain"` (the default), `"json"`,
+//! `"logfmt"`, or `{ pattern = "..." }`, and
+//! an optional `min_severity`, which is
+//! checked after the logger's.
+//! 
+//! * `stdout`, `stderr`
+//! * `file`          - `path`, and optionally `append`
+//!                     and `create_dirs`.
+//! * `rotating_file` - `path`, and optionally `max_size`,
+//!                     `rotation` (`"never"`, `"hourly"` or
+//!                     `"daily"`), `dated` (a `chrono` format
+//!                     string to name archives with),
+//!                     `max_archives` and `create_dirs`.
+//! 
+//! # Examples
+//! 
+//! ```no_run
+//! # use loggerithm::logger;
+//! # logger!(super);
+//! fn main() {
+//!     loggerithm::config::load("logging.toml").unwrap();
+//! }
+//! ```
+//! 
+//! Use `watch` to apply changes to the file
+//! while the program runs.
+
+
+
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Sender, RecvTimeoutError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use serde::Deserialize;
+use static_init::dynamic;
+
+use crate::LogContext;
+use crate::format::{Formatter, Json, Logfmt, Pattern, PatternError};
+use crate::internal::{self, LoggerLocation, RegisteredLogger};
+use crate::logger::Logger;
+use crate::target::{Target, Filtered, Stdout, Stderr, File, RotatingFile, Rotation, Naming};
+use crate::target::file;
+
+
+
+/// The loggers that modules had before a
+/// configuration replaced them.
+/// 
+/// The key is the module path.
+/// 
+/// They are registered again when a later
+/// configuration no longer has the module.
+#[dynamic]
+static mut ORIGINALS : HashMap<String, RegisteredLogger> = HashMap::new();
+
+
+
+/// Read a configuration file, then apply it.
+/// 
+/// The file is read as JSON if it ends in
+/// `.json`, and as TOML otherwise.
+/// 
+/// # Arguments
+/// 
+/// * `path` - The path of the file.
+/// 
+/// # Returns
+/// 
+/// The first problem found in the file,
+/// if there is one.
+pub fn load<P>(path : P) -> Result<(), ConfigError>
+    where P : AsRef<Path>
+{
+    return Config::read(path)?.apply();
+}
+
+
+
+/// Load a configuration file, then keep
+/// checking it for changes and apply them
+/// while the program runs.
+/// 
+/// If a changed file has a problem, it is
+/// printed to the standard error stream,
+/// and the previous configuration is kept.
+/// 
+/// # Arguments
+/// 
+/// * `path`     - The path of the file.
+/// * `interval` - How often to check the file.
+/// 
+/// # Returns
+/// 
+/// A watcher that stops checking the file
+/// when dropped, or the first problem found
+/// in the file when first loaded.
+/// 
+/// # Examples
+/// 
+/// ```no_run
+/// # use loggerithm::logger;
+/// # logger!(super);
+/// use std::time::Duration;
+/// fn main() {
+///     let _watcher = loggerithm::config::watch("logging.toml", Duration::from_secs(5)).unwrap();
+/// }
+/// ```
+pub fn watch<P>(path : P, interval : Duration) -> Result<Watcher, ConfigError>
+    where P : AsRef<Path>
+{
+    let path     = path.as_ref().to_path_buf();
+    let mut last = Config::read(&path)?;
+    last.apply()?;
+    let mut modified = fs::metadata(&path).and_then(|meta| meta.modified()).ok();
+    let (sender, receiver) = mpsc::channel::<()>();
+    let thread_path = path.clone();
+    let handle = thread::Builder::new()
+        .name(String::from("loggerithm-config"))
+        .spawn(move || {
+            while let Err(RecvTimeoutError::Timeout) = receiver.recv_timeout(interval) {
+                let current = fs::metadata(&thread_path).and_then(|meta| meta.modified()).ok();
+                if (current == modified) {
+                    continue;
+                }
+                modified = current;
+                match (Config::read(&thread_path).and_then(|config| config.apply_over(Some(&last)).map(|_| config))) {
+                    Ok(config) => last = config,
+                    Err(error) => eprintln!("Failed to reload `{}`: {}", thread_path.display(), error)
+                }
+            }
+        })
+        .map_err(|error| ConfigError::Read {path : path.clone(), error})?;
+    return Ok(Watcher {
+        path,
+        sender : Some(sender),
+        handle : Some(handle)
+    });
+}
+
+/// Checks a configuration file for changes
+/// on a background thread, until dropped.
+/// 
+/// Created by `watch`.
+pub struct Watcher {
+    path   : PathBuf,
+    sender : Option<Sender<()>>,
+    handle : Option<JoinHandle<()>>
+}
+/// Data access functions.
+impl Watcher {
+    /// Get the path of the file.
+    pub fn path(&self) -> &Path {
+        return &self.path;
+    }
+}
+impl Drop for Watcher {
+    fn drop(&mut self) {
+        self.sender.take();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+
+
+/// A parsed configuration file.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    #[serde(default)]
+    severities : BTreeMap<String, LevelConfig>,
+    #[serde(default)]
+    loggers    : BTreeMap<String, LoggerConfig>
+}
+/// Initialisation.
+impl Config {
+    /// Read a configuration file.
+    /// 
+    /// The file is read as JSON if it ends in
+    /// `.json`, and as TOML otherwise.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `path` - The path of the file.
+    pub fn read<P>(path : P) -> Result<Config, ConfigError>
+        where P : AsRef<Path>
+    {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|error| ConfigError::Read {
+            path : path.to_path_buf(),
+            error
+        })?;
+        return if (path.extension().is_some_and(|extension| extension == "json")) {
+            Config::from_json(&text)
+        } else {
+            Config::from_toml(&text)
+        };
+    }
+    /// Parse a TOML configuration.
+    pub fn from_toml(text : &str) -> Result<Config, ConfigError> {
+        return toml::from_str(text).map_err(|error| ConfigError::Parse {
+            message : error.to_string()
+        });
+    }
+    /// Parse a JSON configuration.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use loggerithm::config::Config;
+    /// let config = Config::from_json(r#"{
+    ///     "loggers" : {
+    ///         "" : {"min_severity" : "warn", "targets" : [{"type" : "stderr", "format" : "logfmt"}]}
+    ///     }
+    /// }"#).unwrap();
+    /// assert!(Config::from_json(r#"{"loggers" : {"" : {"targets" : [{"type" : "socket"}]}}}"#).is_err());
+    /// ```
+    pub fn from_json(text : &str) -> Result<Config, ConfigError> {
+        return serde_json::from_str(text).map_err(|error| ConfigError::Parse {
+            message : error.to_string()
+        });
+    }
+}
+impl Config {
+    /// Build every logger, then register
+    /// them and set the minimum severities.
+    /// 
+    /// Every logger and severity is changed
+    /// at the same time, and nothing is
+    /// changed if there is a problem with
+    /// any of them. See `loggerithm::reload`.
+    pub fn apply(&self) -> Result<(), ConfigError> {
+        return self.apply_over(None);
+    }
+    /// Apply the configuration, removing the
+    /// minimum severities set by a previous
+    /// configuration that are not set by this
+    /// one.
+    /// 
+    /// Modules with a logger from an earlier
+    /// configuration that are not in this one
+    /// get back the logger they had before.
+    fn apply_over(&self, previous : Option<&Config>) -> Result<(), ConfigError> {
+        let mut severities = Vec::new();
+        for (module, level) in &self.severities {
+            let key = format!("severities.\"{}\"", module);
+            check_module(&key, module)?;
+            severities.push((module.clone(), Some(level.severity(&key)?)));
+        }
+        if let Some(previous) = previous {
+            for module in previous.severities.keys() {
+                if (! self.severities.contains_key(module)) {
+                    severities.push((module.clone(), None));
+                }
+            }
+        }
+        let mut loggers = Vec::new();
+        for (module, config) in &self.loggers {
+            let key = format!("loggers.\"{}\"", module);
+            check_module(&key, module)?;
+            loggers.push((module.clone(), RegisteredLogger::from(LoggerLocation::Here(config.build(&key)?))));
+        }
+        let mut originals = ORIGINALS.write();
+        for (module, _) in &loggers {
+            if (! originals.contains_key(module)) {
+                if let Some(original) = internal::LOGGERS.read().get(module) {
+                    originals.insert(module.clone(), original.clone());
+                }
+            }
+        }
+        let removed = originals.keys()
+            .filter(|module| ! self.loggers.contains_key(*module))
+            .cloned().collect::<Vec<_>>();
+        for module in removed {
+            if let Some(original) = originals.remove(&module) {
+                loggers.push((module, original));
+            }
+        }
+        internal::reload(loggers, severities);
+        return Ok(());
+    }
+}
+
+/// Check that a module has a registered
+/// logger.
+fn check_module(key : &str, module : &str) -> Result<(), ConfigError> {
+    if (! internal::LOGGERS.read().contains_key(module)) {
+        return Err(ConfigError::UnknownModule {
+            key    : String::from(key),
+            module : String::from(module)
+        });
+    }
+    return Ok(());
+}
+
+
+/// A level, by name or severity.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+enum LevelConfig {
+    Severity(u32),
+    Name(String)
+}
+impl LevelConfig {
+    /// Get the severity of the level.
+    fn severity(&self, key : &str) -> Result<u32, ConfigError> {
+        return match (self) {
+            LevelConfig::Severity(severity) => Ok(*severity),
+            LevelConfig::Name(name) => internal::parse_level(name).ok_or_else(|| ConfigError::UnknownLevel {
+                key   : String::from(key),
+                level : name.clone()
+            })
+        };
+    }
+}
+
+
+/// A logger.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LoggerConfig {
+    min_severity : Option<LevelConfig>,
+    #[serde(default)]
+    targets      : Vec<TargetConfig>
+}
+impl LoggerConfig {
+    /// Build the logger.
+    fn build(&self, key : &str) -> Result<Logger, ConfigError> {
+        let mut logger = Logger::new();
+        if let Some(level) = &self.min_severity {
+            let min_severity = level.severity(&format!("{}.min_severity", key))?;
+            logger = logger.set_min_severity(|| min_severity);
+        }
+        for (index, target) in self.targets.iter().enumerate() {
+            logger = target.add_to(logger, &format!("{}.targets[{}]", key, index))?;
+        }
+        return Ok(logger);
+    }
+}
+
+
+/// A target.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+enum TargetConfig {
+    Stdout {
+        #[serde(default)]
+        format       : FormatConfig,
+        min_severity : Option<LevelConfig>
+    },
+    Stderr {
+        #[serde(default)]
+        format       : FormatConfig,
+        min_severity : Option<LevelConfig>
+    },
+    File {
+        path         : PathBuf,
+        #[serde(default)]
+        format       : FormatConfig,
+        min_severity : Option<LevelConfig>,
+        append       : Option<bool>,
+        create_dirs  : Option<bool>
+    },
+    RotatingFile {
+        path         : PathBuf,
+        #[serde(default)]
+        format       : FormatConfig,
+        min_severity : Option<LevelConfig>,
+        max_size     : Option<u64>,
+        rotation     : Option<RotationConfig>,
+        dated        : Option<String>,
+        max_archives : Option<usize>,
+        create_dirs  : Option<bool>
+    }
+}
+impl TargetConfig {
+    /// Build the target, and add it to
+    /// a logger.
+    fn add_to(&self, logger : Logger, key : &str) -> Result<Logger, ConfigError> {
+        let open_error = |path : &Path| {
+            let key = String::from(key);
+            let path = path.to_path_buf();
+            return move |error| ConfigError::Open {key, path, error};
+        };
+        let min_severity = match (self.min_severity()) {
+            Some(level) => Some(level.severity(&format!("{}.min_severity", key))?),
+            None        => None
+        };
+        return Ok(match (self) {
+            TargetConfig::Stdout {format, ..} => {
+                let formatter = format.build(key)?;
+                add_target(logger, min_severity, Stdout::new()
+                    .set_formatter(move |context : &LogContext| formatter.format(context))
+                )
+            },
+            TargetConfig::Stderr {format, ..} => {
+                let formatter = format.build(key)?;
+                add_target(logger, min_severity, Stderr::new()
+                    .set_formatter(move |context : &LogContext| formatter.format(context))
+                )
+            },
+            TargetConfig::File {path, format, append, create_dirs, ..} => {
+                let formatter = format.build(key)?;
+                let mut options = File::options()
+                    .set_formatter(move |context : &LogContext| formatter.format(context));
+                if let Some(append) = append {
+                    options = options.set_append(*append);
+                }
+                if let Some(create_dirs) = create_dirs {
+                    options = options.set_create_dirs(*create_dirs);
+                }
+                add_target(logger, min_severity, options.open(path).map_err(open_error(path))?)
+            },
+            TargetConfig::RotatingFile {path, format, max_size, rotation, dated, max_archives, create_dirs, ..} => {
+                let formatter = format.build(key)?;
+                let mut options = RotatingFile::options()
+                    .set_formatter(move |context : &LogContext| formatter.format(context));
+                if let Some(max_size) = max_size {
+                    options = options.set_max_size(*max_size);
+                }
+                if let Some(rotation) = rotation {
+                    options = options.set_rotation(rotation.rotation());
+                }
+                if let Some(dated) = dated {
+                    options = options.set_naming(Naming::Dated(dated.clone()));
+                }
+                if let Some(max_archives) = max_archives {
+                    options = options.set_max_archives(*max_archives);
+                }
+                if let Some(create_dirs) = create_dirs {
+                    options = options.set_create_dirs(*create_dirs);
+                }
+                add_target(logger, min_severity, options.open(path).map_err(open_error(path))?)
+            }
+        });
+    }
+    /// Get the minimum severity of the
+    /// target, if it has one.
+    fn min_severity(&self) -> Option<&LevelConfig> {
+        return match (self) {
+            TargetConfig::Stdout       {min_severity, ..} => min_severity.as_ref(),
+            TargetConfig::Stderr       {min_severity, ..} => min_severity.as_ref(),
+            TargetConfig::File         {min_severity, ..} => min_severity.as_ref(),
+            TargetConfig::RotatingFile {min_severity, ..} => min_severity.as_ref()
+        };
+    }
+}
+
+/// Add a target to a logger, wrapped in a
+/// `Filtered` if it has a minimum severity.
+fn add_target<T : 'static>(logger : Logger, min_severity : Option<u32>, target : T) -> Logger
+    where T : Target
+{
+    return match (min_severity) {
+        Some(min_severity) => logger.with_target(Filtered::new(target).set_min_severity(|| min_severity)),
+        None               => logger.with_target(target)
+    };
+}
+
+
+/// The format of a target.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+enum FormatConfig {
+    #[default]
+    Plain,
+    Json,
+    Logfmt,
+    Pattern(String)
+}
+impl FormatConfig {
+    /// Build the formatter.
+    fn build(&self, key : &str) -> Result<Box<dyn Formatter>, ConfigError> {
+        return Ok(match (self) {
+            FormatConfig::Plain            => Box::new(file::plain),
+            FormatConfig::Json             => Box::new(Json::new()),
+            FormatConfig::Logfmt           => Box::new(Logfmt::new()),
+            FormatConfig::Pattern(pattern) => Box::new(Pattern::new(pattern).map_err(|error| ConfigError::InvalidPattern {
+                key : format!("{}.format", key),
+                error
+            })?)
+        });
+    }
+}
+
+
+/// When a rotating file rotates.
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum RotationConfig {
+    Never,
+    Hourly,
+    Daily
+}
+impl RotationConfig {
+    /// Get the rotation.
+    fn rotation(&self) -> Rotation {
+        return match (self) {
+            RotationConfig::Never  => Rotation::Never,
+            RotationConfig::Hourly => Rotation::Hourly,
+            RotationConfig::Daily  => Rotation::Daily
+        };
+    }
+}
+
+
+
+/// A problem found while loading a
+/// configuration file.
+/// 
+/// Each key is the path to the problem in
+/// the file, such as `loggers."app".targets[0]`.
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The file could not be read.
+    Read {
+        path  : PathBuf,
+        error : io::Error
+    },
+    /// The file is not valid TOML or JSON,
+    /// or does not match the format.
+    Parse {
+        message : String
+    },
+    /// A level name that is not registered.
+    UnknownLevel {
+        key   : String,
+        level : String
+    },
+    /// A module without a logger registered
+    /// with `logger!`.
+    UnknownModule {
+        key    : String,
+        module : String
+    },
+    /// An invalid pattern format.
+    InvalidPattern {
+        key   : String,
+        error : PatternError
+    },
+    /// A target file could not be opened.
+    Open {
+        key   : String,
+        path  : PathBuf,
+        error : io::Error
+    }
+}
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        return match (self) {
+            ConfigError::Read {path, error} => write!(f,
+                "Failed to read `{}`: {}",
+                path.display(), error
+            ),
+            ConfigError::Parse {message} => write!(f,
+                "Invalid configuration: {}",
+                message
+            ),
+            ConfigError::UnknownLevel {key, level} => write!(f,
+                "Unknown level `{}` at `{}`.",
+                level, key
+            ),
+            ConfigError::UnknownModule {key, module} => write!(f,
+                "Unknown module `{}` at `{}`. The module must have a logger registered with `logger!`.",
+                module, key
+            ),
+            ConfigError::InvalidPattern {key, error} => write!(f,
+                "Invalid pattern at `{}`: {}",
+                key, error
+            ),
+            ConfigError::Open {key, path, error} => write!(f,
+                "Failed to open `{}` at `{}`: {}",
+                path.display(), key, error
+            )
+        };
+    }
+}
+impl Error for ConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        return match (self) {
+            ConfigError::Read {error, ..}           => Some(error),
+            ConfigError::InvalidPattern {error, ..} => Some(error),
+            ConfigError::Open {error, ..}           => Some(error),
+            _                                       => None
+        };
+    }
+}
@@ -0,0 +1,232 @@
+//! Everything related to setting the
+//! minimum severity of modules from an
+//! environment variable.
+//! 
+//! The variable is a comma separated list
+//! of directives:
+//! 
+//! * `LEVEL`        - Sets the minimum severity of
+//!                    every module.
+//! * `MODULE=LEVEL` - Sets the minimum severity of a
+//!                    module and its submodules.
+//! * `MODULE`       - Logs everything in a module and
+//!                    its submodules. The module, or one
+//!                    of its submodules, must have a logger
+//!                    registered with `logger!`.
+//! 
+//! `LEVEL` is the name of any registered
+//! level, including custom ones, ignoring
+//! case. It can also be a severity number,
+//! or `off` to log nothing.
+//! 
+//! # Examples
+//! 
+//! ```text
+//! LOGGERITHM=info,app::db=trace,hyper=warn
+//! ```
+//! ```
+//! # use loggerithm::logger;
+//! # logger!(super);
+//! fn main() {
+//!     loggerithm::env::init().unwrap();
+//! }
+//! ```
+
+
+
+use std::env;
+use std::error::Error;
+use std::fmt;
+
+use static_init::dynamic;
+
+use crate::internal;
+
+
+
+/// The name of the environment variable
+/// read by `init`.
+pub const DEFAULT_VAR : &str = "LOGGERITHM";
+
+/// The modules given a minimum severity by
+/// the last call to `apply`, which are
+/// cleared by the next call.
+#[dynamic]
+static mut APPLIED : Vec<String> = Vec::new();
+
+
+
+/// Read the `LOGGERITHM` environment variable,
+/// and apply its directives.
+/// 
+/// See `init_from`.
+pub fn init() -> Result<(), EnvError> {
+    return init_from(DEFAULT_VAR);
+}
+
+/// Read an environment variable, and apply
+/// its directives.
+/// 
+/// Nothing is changed if the variable is
+/// not set, or if any directive is invalid.
+/// 
+/// # Arguments
+/// 
+/// * `var` - The name of the environment variable.
+/// 
+/// # Returns
+/// 
+/// An error describing the first invalid
+/// directive, if there is one.
+pub fn init_from(var : &str) -> Result<(), EnvError> {
+    return match (env::var(var)) {
+        Ok(directives)                    => apply(&directives),
+        Err(env::VarError::NotPresent)    => Ok(()),
+        Err(env::VarError::NotUnicode(_)) => Err(EnvError::NotUnicode {
+            var : String::from(var)
+        })
+    };
+}
+
+/// Apply a list of directives, replacing
+/// the minimum severities set by the last
+/// call.
+/// 
+/// Nothing is changed if any directive
+/// is invalid.
+/// 
+/// # Arguments
+/// 
+/// * `directives` - The directives, such as
+///                  `info,app::db=trace`.
+/// 
+/// # Returns
+/// 
+/// An error describing the first invalid
+/// directive, if there is one.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use loggerithm::logger;
+/// # logger!(super);
+/// # fn main() {
+/// loggerithm::env::apply("warn,my_app::db=debug").unwrap();
+/// assert!(loggerithm::env::apply("my_app=loud").is_err());
+/// assert!(loggerithm::env::apply("wran").is_err());
+/// # }
+/// ```
+pub fn apply(directives : &str) -> Result<(), EnvError> {
+    let parsed         = parse(directives)?;
+    let mut applied    = APPLIED.write();
+    let mut severities = applied.drain(..).map(|module| (module, None)).collect::<Vec<_>>();
+    for (module, min_severity) in parsed {
+        applied.push(module.clone());
+        severities.push((module, Some(min_severity)));
+    }
+    internal::reload(Vec::new(), severities);
+    return Ok(());
+}
+
+/// Parse a list of directives.
+/// 
+/// # Arguments
+/// 
+/// * `directives` - The directives, such as
+///                  `info,app::db=trace`.
+/// 
+/// # Returns
+/// 
+/// The module path and minimum severity of
+/// each directive, in order. The module path
+/// is empty for directives without one.
+pub fn parse(directives : &str) -> Result<Vec<(String, u32)>, EnvError> {
+    let mut parsed = Vec::new();
+    for directive in directives.split(',').map(str::trim).filter(|directive| ! directive.is_empty()) {
+        parsed.push(match (directive.split_once('=')) {
+            Some((module, level)) => {
+                let module = module.trim();
+                if (module.is_empty()) {
+                    return Err(EnvError::MissingModule {
+                        directive : String::from(directive)
+                    });
+                }
+                (String::from(module), parse_level(directive, level.trim())?)
+            },
+            None => match (internal::parse_level(directive)) {
+                Some(min_severity) => (String::new(), min_severity),
+                None if (is_registered(directive)) => (String::from(directive), 0),
+                None => return Err(EnvError::UnknownDirective {
+                    directive : String::from(directive)
+                })
+            }
+        });
+    }
+    return Ok(parsed);
+}
+
+/// Check if a module, or one of its
+/// submodules, has a registered logger.
+fn is_registered(module : &str) -> bool {
+    let prefix = format!("{}::", module);
+    return internal::LOGGERS.read().keys()
+        .any(|registered| registered == module || registered.starts_with(&prefix));
+}
+
+/// Parse the level of a directive.
+fn parse_level(directive : &str, level : &str) -> Result<u32, EnvError> {
+    return internal::parse_level(level).ok_or_else(|| EnvError::UnknownLevel {
+        directive : String::from(directive),
+        level     : String::from(level)
+    });
+}
+
+
+
+/// A problem found while reading directives.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EnvError {
+    /// A level name that is not registered.
+    UnknownLevel {
+        directive : String,
+        level     : String
+    },
+    /// A directive without a `=` that is
+    /// neither a level nor a module with
+    /// a registered logger.
+    UnknownDirective {
+        directive : String
+    },
+    /// A `=LEVEL` directive without a module.
+    MissingModule {
+        directive : String
+    },
+    /// An environment variable that is not
+    /// valid unicode.
+    NotUnicode {
+        var : String
+    }
+}
+impl fmt::Display for EnvError {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        return match (self) {
+            EnvError::UnknownLevel {directive, level} => write!(f,
+                "Unknown level `{}` in directive `{}`.",
+                level, directive
+            ),
+            EnvError::UnknownDirective {directive} => write!(f,
+                "Unknown level or module `{}`.",
+                directive
+            ),
+            EnvError::MissingModule {directive} => write!(f,
+                "Missing module in directive `{}`. Use `MODULE=LEVEL`.",
+                directive
+            ),
+            EnvError::NotUnicode {var} => write!(f,
+                "Environment variable `{}` is not valid unicode.",
+                var
+            )
+        };
+    }
+}
+impl Error for EnvError {}
@@ -0,0 +1,166 @@
+//! Everything related to structured
+//! fields attached to log messages.
+//! 
+//! # Examples
+//! 
+//! ```
+//! # use loggerithm::{logger, log};
+//! # use loggerithm::level::INFO;
+//! # logger!(super);
+//! # fn main() {
+//! let path = std::path::Path::new("/index.html");
+//! log!(INFO, user_id = 42, path = ?path, ok = true; "Served {} bytes", 1024);
+//! # }
+//! ```
+
+
+
+use std::fmt;
+
+
+
+/// The value of a structured field.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    /// A boolean.
+    Bool(bool),
+    /// A signed integer.
+    I64(i64),
+    /// An unsigned integer.
+    U64(u64),
+    /// A floating point number.
+    F64(f64),
+    /// A string.
+    Str(String)
+}
+/// Initialisation.
+impl Value {
+    /// Create a string value using the
+    /// `Display` implementation of a value.
+    /// 
+    /// Used by `%` in the `log!` macro.
+    pub fn display<T>(value : &T) -> Value
+        where T : fmt::Display + ?Sized
+    {
+        return Value::Str(value.to_string());
+    }
+    /// Create a string value using the
+    /// `Debug` implementation of a value.
+    /// 
+    /// Used by `?` in the `log!` macro.
+    pub fn debug<T>(value : &T) -> Value
+        where T : fmt::Debug + ?Sized
+    {
+        return Value::Str(format!("{:?}", value));
+    }
+}
+/// Data access functions.
+impl Value {
+    /// Get the value as a boolean, if
+    /// it is one.
+    pub fn as_bool(&self) -> Option<bool> {
+        return match (self) {
+            Value::Bool(value) => Some(*value),
+            _                  => None
+        };
+    }
+    /// Get the value as a signed integer,
+    /// if it is an integer that fits.
+    pub fn as_i64(&self) -> Option<i64> {
+        return match (self) {
+            Value::I64(value) => Some(*value),
+            Value::U64(value) => i64::try_from(*value).ok(),
+            _                 => None
+        };
+    }
+    /// Get the value as an unsigned integer,
+    /// if it is an integer that fits.
+    pub fn as_u64(&self) -> Option<u64> {
+        return match (self) {
+            Value::I64(value) => u64::try_from(*value).ok(),
+            Value::U64(value) => Some(*value),
+            _                 => None
+        };
+    }
+    /// Get the value as a floating point
+    /// number, if it is a number.
+    pub fn as_f64(&self) -> Option<f64> {
+        return match (self) {
+            Value::I64(value) => Some(*value as f64),
+            Value::U64(value) => Some(*value as f64),
+            Value::F64(value) => Some(*value),
+            _                 => None
+        };
+    }
+    /// Get the value as a string, if
+    /// it is one.
+    pub fn as_str(&self) -> Option<&str> {
+        return match (self) {
+            Value::Str(value) => Some(value),
+            _                 => None
+        };
+    }
+}
+impl fmt::Display for Value {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        return match (self) {
+            Value::Bool(value) => write!(f, "{}", value),
+            Value::I64(value)  => write!(f, "{}", value),
+            Value::U64(value)  => write!(f, "{}", value),
+            Value::F64(value)  => write!(f, "{}", value),
+            Value::Str(value)  => write!(f, "{}", value)
+        };
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value : bool) -> Value {return Value::Bool(value);}
+}
+impl From<i8> for Value {
+    fn from(value : i8) -> Value {return Value::I64(value as i64);}
+}
+impl From<i16> for Value {
+    fn from(value : i16) -> Value {return Value::I64(value as i64);}
+}
+impl From<i32> for Value {
+    fn from(value : i32) -> Value {return Value::I64(value as i64);}
+}
+impl From<i64> for Value {
+    fn from(value : i64) -> Value {return Value::I64(value);}
+}
+impl From<isize> for Value {
+    fn from(value : isize) -> Value {return Value::I64(value as i64);}
+}
+impl From<u8> for Value {
+    fn from(value : u8) -> Value {return Value::U64(value as u64);}
+}
+impl From<u16> for Value {
+    fn from(value : u16) -> Value {return Value::U64(value as u64);}
+}
+impl From<u32> for Value {
+    fn from(value : u32) -> Value {return Value::U64(value as u64);}
+}
+impl From<u64> for Value {
+    fn from(value : u64) -> Value {return Value::U64(value);}
+}
+impl From<usize> for Value {
+    fn from(value : usize) -> Value {return Value::U64(value as u64);}
+}
+impl From<f32> for Value {
+    fn from(value : f32) -> Value {return Value::F64(value as f64);}
+}
+impl From<f64> for Value {
+    fn from(value : f64) -> Value {return Value::F64(value);}
+}
+impl From<char> for Value {
+    fn from(value : char) -> Value {return Value::Str(value.to_string());}
+}
+impl From<&str> for Value {
+    fn from(value : &str) -> Value {return Value::Str(String::from(value));}
+}
+impl From<String> for Value {
+    fn from(value : String) -> Value {return Value::Str(value);}
+}
+impl From<&String> for Value {
+    fn from(value : &String) -> Value {return Value::Str(value.clone());}
+}
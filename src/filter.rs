@@ -0,0 +1,267 @@
+//! Everything related to deciding which
+//! log messages are written.
+//! 
+//! Filters can be given to a `Logger`, or
+//! to a `target::Filtered`, and are checked
+//! before the message is written.
+//! 
+//! # Examples
+//! 
+//! ```no_run
+//! # use loggerithm::{logger, log};
+//! # use loggerithm::logger::Logger;
+//! # use loggerithm::level::{INFO, WARN, ERROR};
+//! use loggerithm::filter::Filter;
+//! use loggerithm::target::{Filtered, Stdout, File};
+//! logger!(Logger::new()
+//!     .set_filter(Filter::module_prefix("hyper").not())
+//!     .with_target(Filtered::new(File::open("logs/db.log").unwrap())
+//!         .set_filter(Filter::module_prefix("app::db")
+//!             .or(Filter::severity_between(WARN::SEVERITY()..))
+//!         )
+//!     )
+//!     .with_target(Filtered::new(Stdout::new())
+//!         .set_filter(Filter::severity_between(INFO::SEVERITY()..ERROR::SEVERITY()))
+//!     )
+//! );
+//! # fn main() {}
+//! ```
+
+
+
+use std::ops::{Bound, RangeBounds};
+use std::sync::Arc;
+
+use crate::LogContext;
+
+
+
+/// A rule that decides if a log message
+/// is written.
+/// 
+/// Filters can be combined with `and`, `or`
+/// and `not`. Any function that takes a
+/// `&LogContext` and returns a `bool` can
+/// be used as a filter.
+/// 
+/// Filters built only from `module_prefix`
+/// and `severity_between`, on a logger or on
+/// a `Filtered` target, are checked before
+/// the message is formatted, so messages
+/// that they reject cost very little.
+#[derive(Clone)]
+pub struct Filter {
+    rule : Arc<FilterRule>
+}
+/// Initialisation.
+impl Filter {
+    /// Create a filter from a function.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `test` - The function that decides. It takes
+    ///            a `&LogContext`, and returns `true`
+    ///            to write the message.
+    /// 
+    /// # Generics
+    /// 
+    /// * `F` - The function that decides.
+    pub fn new<F : 'static>(test : F) -> Filter
+        where F : Fn(&LogContext) -> bool + Send + Sync
+    {
+        return Filter::from_rule(FilterRule::Test(Box::new(test)));
+    }
+    /// Create a filter from a rule.
+    fn from_rule(rule : FilterRule) -> Filter {
+        return Filter {
+            rule : Arc::new(rule)
+        };
+    }
+    /// Create a filter that lets messages
+    /// logged in a module, or any of its
+    /// submodules, through.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `module` - The module path, such as
+    ///              `"app::db"`. This matches
+    ///              `app::db::pool`, but not
+    ///              `app::dbx`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// # use loggerithm::logger::Logger;
+    /// # use loggerithm::level::INFO;
+    /// use loggerithm::filter::Filter;
+    /// let logger  = Logger::new();
+    /// let context = |module : &str| logger.create_context(&INFO::LEVEL, String::from(module), (0, 0), String::new());
+    /// let filter  = Filter::module_prefix("app::db");
+    /// assert!(filter.matches(&context("app::db")));
+    /// assert!(filter.matches(&context("app::db::pool")));
+    /// assert!(! filter.matches(&context("app::dbx")));
+    /// assert!(filter.not().matches(&context("app")));
+    /// ```
+    pub fn module_prefix(module : &str) -> Filter {
+        return Filter::from_rule(FilterRule::ModulePrefix(String::from(module)));
+    }
+    /// Create a filter that lets messages
+    /// with a severity in a range through.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `severities` - The range of severities, such
+    ///                  as `INFO::SEVERITY()..` or
+    ///                  `DEBUG::SEVERITY()..=WARN::SEVERITY()`.
+    /// 
+    /// # Generics
+    /// 
+    /// * `R` - The range.
+    pub fn severity_between<R>(severities : R) -> Filter
+        where R : RangeBounds<u32>
+    {
+        return Filter::from_rule(FilterRule::SeverityBetween(
+            severities.start_bound().cloned(),
+            severities.end_bound().cloned()
+        ));
+    }
+    /// Create a filter that lets messages
+    /// containing some text through.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `text` - The text to look for.
+    pub fn message_contains(text : &str) -> Filter {
+        let text = String::from(text);
+        return Filter::new(move |context : &LogContext| context.message().contains(&text));
+    }
+    /// Create a filter that lets messages
+    /// matching a regular expression through.
+    /// 
+    /// Requires the `regex` feature.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `regex` - The regular expression. It can
+    ///             match any part of the message.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use loggerithm::filter::Filter;
+    /// use regex::Regex;
+    /// let filter = Filter::message_matches(Regex::new("^user [0-9]+ ").unwrap());
+    /// ```
+    #[cfg(feature = "regex")]
+    pub fn message_matches(regex : regex::Regex) -> Filter {
+        return Filter::new(move |context : &LogContext| regex.is_match(&context.message()));
+    }
+}
+/// Combination functions.
+impl Filter {
+    /// Create a filter that lets messages
+    /// through if both filters do.
+    /// 
+    /// `other` is not checked if this filter
+    /// does not let the message through.
+    pub fn and<F>(self, other : F) -> Filter
+        where F : Into<Filter>
+    {
+        return Filter::from_rule(FilterRule::And(self, other.into()));
+    }
+    /// Create a filter that lets messages
+    /// through if either filter does.
+    /// 
+    /// `other` is not checked if this filter
+    /// lets the message through.
+    pub fn or<F>(self, other : F) -> Filter
+        where F : Into<Filter>
+    {
+        return Filter::from_rule(FilterRule::Or(self, other.into()));
+    }
+    /// Create a filter that lets messages
+    /// through if this filter does not.
+    pub fn not(self) -> Filter {
+        return Filter::from_rule(FilterRule::Not(self));
+    }
+}
+/// Data access functions.
+impl Filter {
+    /// Check if the filter lets a message
+    /// through.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `context` - A `LogContext` containing information
+    ///               about the log.
+    pub fn matches(&self, context : &LogContext) -> bool {
+        return match (&*self.rule) {
+            FilterRule::Test(test)                  => test(context),
+            FilterRule::ModulePrefix(module)        => module_matches(module, &context.module()),
+            FilterRule::SeverityBetween(start, end) => (*start, *end).contains(&context.level().get_severity()),
+            FilterRule::And(a, b)                   => a.matches(context) && b.matches(context),
+            FilterRule::Or(a, b)                    => a.matches(context) || b.matches(context),
+            FilterRule::Not(filter)                 => ! filter.matches(context)
+        };
+    }
+    /// Check if the filter lets a message
+    /// through, using only its severity and
+    /// module, before it is formatted.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `severity` - The severity of the message.
+    /// * `module`   - The module that the message
+    ///                is logged in.
+    /// 
+    /// # Returns
+    /// 
+    /// `None` if the answer depends on the
+    /// rest of the message.
+    pub(crate) fn matches_metadata(&self, severity : u32, module : &str) -> Option<bool> {
+        return match (&*self.rule) {
+            FilterRule::Test(_)                     => None,
+            FilterRule::ModulePrefix(prefix)        => Some(module_matches(prefix, module)),
+            FilterRule::SeverityBetween(start, end) => Some((*start, *end).contains(&severity)),
+            FilterRule::And(a, b) => match (a.matches_metadata(severity, module), b.matches_metadata(severity, module)) {
+                (Some(false), _) | (_, Some(false)) => Some(false),
+                (Some(true), Some(true))            => Some(true),
+                _                                   => None
+            },
+            FilterRule::Or(a, b) => match (a.matches_metadata(severity, module), b.matches_metadata(severity, module)) {
+                (Some(true), _) | (_, Some(true)) => Some(true),
+                (Some(false), Some(false))        => Some(false),
+                _                                 => None
+            },
+            FilterRule::Not(filter) => filter.matches_metadata(severity, module).map(|matches| ! matches)
+        };
+    }
+}
+impl<F : 'static> From<F> for Filter
+    where F : Fn(&LogContext) -> bool + Send + Sync
+{
+    fn from(test : F) -> Filter {
+        return Filter::new(test);
+    }
+}
+
+
+
+/// How a `Filter` decides.
+enum FilterRule {
+    Test(Box<dyn Fn(&LogContext) -> bool + Send + Sync>),
+    ModulePrefix(String),
+    SeverityBetween(Bound<u32>, Bound<u32>),
+    And(Filter, Filter),
+    Or(Filter, Filter),
+    Not(Filter)
+}
+
+/// Check if a module is a submodule of,
+/// or the same as, another.
+fn module_matches(prefix : &str, module : &str) -> bool {
+    return match (module.strip_prefix(prefix)) {
+        Some(rest) => prefix.is_empty() || rest.is_empty() || rest.starts_with("::"),
+        None       => false
+    };
+}
@@ -0,0 +1,66 @@
+//! Everything related to formatting log
+//! messages as text.
+//! 
+//! # Examples
+//! 
+//! ```no_run
+//! # use loggerithm::{logger, log};
+//! # use loggerithm::logger::Logger;
+//! # use loggerithm::level::INFO;
+//! use loggerithm::format::Json;
+//! use loggerithm::target::File;
+//! logger!(Logger::new()
+//!     .with_target(File::options()
+//!         .set_formatter(Json::new())
+//!         .open("logs/app.jsonl")
+//!         .unwrap()
+//!     )
+//! );
+//! # fn main() {
+//! log!(INFO, "This is written to `logs/app.jsonl` as JSON.");
+//! # }
+//! ```
+
+
+
+use crate::LogContext;
+
+pub mod json;
+pub use json::Json;
+pub mod logfmt;
+pub use logfmt::Logfmt;
+pub mod pattern;
+pub use pattern::{Pattern, PatternError};
+
+
+
+/// Something that turns a log message
+/// into a line of text.
+/// 
+/// This is implemented for any function
+/// that takes a `&LogContext` and returns
+/// a `String`.
+/// 
+/// Formatters are shared between threads,
+/// so they must be `Send` and `Sync`.
+pub trait Formatter : Send + Sync {
+    /// Format a log message.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `context` - A `LogContext` containing information
+    ///               about the log.
+    /// 
+    /// # Returns
+    /// 
+    /// The formatted line, without a newline
+    /// at the end.
+    fn format(&self, context : &LogContext) -> String;
+}
+impl<F> Formatter for F
+    where F : Fn(&LogContext) -> String + Send + Sync
+{
+    fn format(&self, context : &LogContext) -> String {
+        return self(context);
+    }
+}
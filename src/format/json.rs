@@ -0,0 +1,103 @@
+//! A formatter that writes JSON Lines.
+
+
+
+use chrono::SecondsFormat;
+
+use crate::LogContext;
+use crate::field::Value;
+use crate::format::Formatter;
+
+
+
+/// A formatter that writes each log
+/// message as a single line JSON object.
+/// 
+/// The span path is written as `span`,
+/// and structured fields are written in
+/// a `fields` object, if there are any.
+/// No colours are included.
+/// 
+/// # Examples
+/// 
+/// ```text
+/// {"time":"2026-10-18T09:21:42.094267Z","module":"app::db","line":12,"column":5,"level":"INFO","severity":20,"message":"Connected.","fields":{"attempt":2}}
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Json {}
+/// Initialisation.
+impl Json {
+    /// Create a new JSON formatter.
+    pub fn new() -> Json {
+        return Json {};
+    }
+}
+impl Formatter for Json {
+    fn format(&self, context : &LogContext) -> String {
+        let mut line = format!(
+            "{{\"time\":{},\"module\":{},\"line\":{},\"column\":{},\"level\":{},\"severity\":{},\"message\":{}",
+            string(&context.time_utc().to_rfc3339_opts(SecondsFormat::Micros, true)),
+            string(&context.module()),
+            context.line(),
+            context.column(),
+            string(&context.level().get_name()),
+            context.level().get_severity(),
+            string(&context.message())
+        );
+        let span = context.span();
+        if (! span.is_empty()) {
+            line.push_str(",\"span\":");
+            line.push_str(&string(&span));
+        }
+        let mut fields = context.fields().peekable();
+        if (fields.peek().is_some()) {
+            line.push_str(",\"fields\":{");
+            for (index, (key, field)) in fields.enumerate() {
+                if (index > 0) {
+                    line.push(',');
+                }
+                line.push_str(&string(key));
+                line.push(':');
+                line.push_str(&value(field));
+            }
+            line.push('}');
+        }
+        line.push('}');
+        return line;
+    }
+}
+
+
+
+/// Quote and escape a JSON string.
+pub(crate) fn string(text : &str) -> String {
+    let mut escaped = String::with_capacity(text.len() + 2);
+    escaped.push('"');
+    for ch in text.chars() {
+        match (ch) {
+            '"'  => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            ch if (ch < ' ' || ch == '\u{7f}') => escaped.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => escaped.push(ch)
+        }
+    }
+    escaped.push('"');
+    return escaped;
+}
+
+/// Write a structured field value as JSON.
+/// 
+/// Numbers that are not finite are
+/// written as `null`.
+pub(crate) fn value(value : &Value) -> String {
+    return match (value) {
+        Value::Bool(value) => value.to_string(),
+        Value::I64(value)  => value.to_string(),
+        Value::U64(value)  => value.to_string(),
+        Value::F64(value)  => if (value.is_finite()) {value.to_string()} else {String::from("null")},
+        Value::Str(value)  => string(value)
+    };
+}
@@ -0,0 +1,95 @@
+//! A formatter that writes logfmt.
+
+
+
+use chrono::SecondsFormat;
+
+use crate::LogContext;
+use crate::format::Formatter;
+
+
+
+/// A formatter that writes each log
+/// message as a line of `key=value` pairs.
+/// 
+/// The span path is written as `span`,
+/// if there is one. Structured fields
+/// are written after the message. Values are quoted and
+/// escaped when needed. No colours are
+/// included.
+/// 
+/// # Examples
+/// 
+/// ```text
+/// ts=2026-10-18T09:21:42.094267Z level=INFO module=app::db line=12 msg="Connected to the database." attempt=2
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Logfmt {}
+/// Initialisation.
+impl Logfmt {
+    /// Create a new logfmt formatter.
+    pub fn new() -> Logfmt {
+        return Logfmt {};
+    }
+}
+impl Formatter for Logfmt {
+    fn format(&self, context : &LogContext) -> String {
+        let mut line = format!(
+            "ts={} level={} module={} line={} msg={}",
+            value(&context.time_utc().to_rfc3339_opts(SecondsFormat::Micros, true)),
+            value(&context.level().get_name()),
+            value(&context.module()),
+            context.line(),
+            value(&context.message())
+        );
+        let span = context.span();
+        if (! span.is_empty()) {
+            line.push_str(" span=");
+            line.push_str(&value(&span));
+        }
+        line.push_str(&fields(context));
+        return line;
+    }
+}
+
+
+
+/// Quote and escape a logfmt value,
+/// if needed.
+pub(crate) fn value(text : &str) -> String {
+    let needs_quotes = text.is_empty() || text.chars().any(|ch|
+        ch == ' ' || ch == '=' || ch == '"' || ch == '\\' || ch.is_control()
+    );
+    if (! needs_quotes) {
+        return String::from(text);
+    }
+    let mut escaped = String::with_capacity(text.len() + 2);
+    escaped.push('"');
+    for ch in text.chars() {
+        match (ch) {
+            '"'  => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            ch if (ch.is_control()) => escaped.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => escaped.push(ch)
+        }
+    }
+    escaped.push('"');
+    return escaped;
+}
+
+/// Write the structured fields of a log
+/// message as `key=value` pairs, each
+/// starting with a space.
+pub(crate) fn fields(context : &LogContext) -> String {
+    let mut line = String::new();
+    for (key, field) in context.fields() {
+        line.push(' ');
+        line.push_str(&value(key));
+        line.push('=');
+        line.push_str(&value(&field.to_string()));
+    }
+    return line;
+}
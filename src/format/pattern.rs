@@ -0,0 +1,428 @@
+//! A formatter built from a template string.
+
+
+
+use std::error::Error;
+use std::fmt;
+
+use chrono::format::{Item, StrftimeItems};
+use colored::{Color, ColoredString, Colorize};
+
+use crate::LogContext;
+use crate::format::{logfmt, Formatter};
+
+
+
+/// The default format used for `{time}`
+/// and `{time_utc}`.
+const DEFAULT_TIME_FORMAT : &str = "%Y-%m-%d %H:%M:%S.%f";
+
+
+
+/// A formatter that fills in a template
+/// string with information about the log.
+/// 
+/// # Placeholders
+/// 
+/// * `{time}`, `{time:FORMAT}`         - The local time. `FORMAT` is a
+///                                       `chrono` format string.
+/// * `{time_utc}`, `{time_utc:FORMAT}` - The UTC time.
+/// * `{module}`, `{module:VARIANT}`    - The module path.
+/// * `{span}`, `{span:VARIANT}`        - The span path, or nothing
+///                                       if not in a span.
+/// * `{level}`, `{level:VARIANT}`      - The level name.
+/// * `{line}`                          - The line number.
+/// * `{column}`                        - The column number.
+/// * `{message}`, `{message:f}`        - The message.
+/// * `{fields}`                        - The structured fields, as
+///                                       `key=value` pairs, each
+///                                       starting with a space.
+/// * `{field:NAME}`                    - The value of a structured
+///                                       field, or nothing.
+/// 
+/// `VARIANT` is one of `p`, `f`, `pf` or `fp`, the
+/// same as the `LogContext` functions. `p` pads the
+/// value, and `f` formats it with the level's
+/// formatting.
+/// 
+/// # Styles
+/// 
+/// Any placeholder can end with `|STYLE`, where
+/// `STYLE` is a comma separated list of colours
+/// and attributes to apply to the value:
+/// 
+/// * A colour, such as `green` or `bright green`.
+/// * A background colour, such as `on blue`.
+/// * `bold`, `dimmed`, `italic`, `underline`,
+///   `reversed` or `strikethrough`.
+/// 
+/// A time format can not contain `|`.
+/// 
+/// Use `{{` and `}}` for literal braces.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use loggerithm::format::Pattern;
+/// let pattern = Pattern::new("{time:%H:%M:%S} {level:pf} {module:p}:{line} {message}").unwrap();
+/// let pattern = Pattern::new("{time|bright green,dimmed} {module:p|green,dimmed} {message:f}").unwrap();
+/// assert!(Pattern::new("{lvl}").is_err());
+/// assert!(Pattern::new("{time|shiny}").is_err());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Pattern {
+    pieces : Vec<PatternPiece>
+}
+/// Initialisation.
+impl Pattern {
+    /// Parse a pattern.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `pattern` - The template string.
+    /// 
+    /// # Returns
+    /// 
+    /// The pattern, or an error describing
+    /// the first problem found in it.
+    pub fn new(pattern : &str) -> Result<Pattern, PatternError> {
+        let mut pieces = Vec::new();
+        let mut text   = String::new();
+        let mut chars  = pattern.char_indices().peekable();
+        while let Some((position, ch)) = chars.next() {
+            match (ch) {
+                '{' if (chars.peek().map(|(_, ch)| *ch) == Some('{')) => {
+                    chars.next();
+                    text.push('{');
+                },
+                '}' if (chars.peek().map(|(_, ch)| *ch) == Some('}')) => {
+                    chars.next();
+                    text.push('}');
+                },
+                '{' => {
+                    let mut placeholder = String::new();
+                    loop {
+                        match (chars.next()) {
+                            Some((_, '}')) => break,
+                            Some((_, ch))  => placeholder.push(ch),
+                            None           => return Err(PatternError::Unclosed {position})
+                        }
+                    }
+                    if (! text.is_empty()) {
+                        pieces.push(PatternPiece::Text(text));
+                        text = String::new();
+                    }
+                    pieces.push(PatternPiece::parse(&placeholder, position)?);
+                },
+                '}' => return Err(PatternError::Unopened {position}),
+                ch  => text.push(ch)
+            }
+        }
+        if (! text.is_empty()) {
+            pieces.push(PatternPiece::Text(text));
+        }
+        return Ok(Pattern {pieces});
+    }
+}
+impl Formatter for Pattern {
+    fn format(&self, context : &LogContext) -> String {
+        let mut line = String::new();
+        for piece in &self.pieces {
+            piece.write(context, &mut line);
+        }
+        return line;
+    }
+}
+
+
+
+/// A part of a parsed pattern.
+#[derive(Clone, Debug)]
+enum PatternPiece {
+    Text(String),
+    Time {
+        utc    : bool,
+        format : String
+    },
+    Module(PatternVariant),
+    Span(PatternVariant),
+    Level(PatternVariant),
+    Line,
+    Column,
+    Message(bool),
+    Fields,
+    Field(String),
+    Styled {
+        piece  : Box<PatternPiece>,
+        styles : Vec<PatternStyle>
+    }
+}
+impl PatternPiece {
+    /// Parse the contents of a placeholder.
+    fn parse(placeholder : &str, position : usize) -> Result<PatternPiece, PatternError> {
+        if let Some((placeholder, style)) = placeholder.split_once('|') {
+            return Ok(PatternPiece::Styled {
+                piece  : Box::new(PatternPiece::parse(placeholder, position)?),
+                styles : style.split(',')
+                    .map(|style| PatternStyle::parse(style, position))
+                    .collect::<Result<_, _>>()?
+            });
+        }
+        let (name, spec) = match (placeholder.split_once(':')) {
+            Some((name, spec)) => (name, Some(spec)),
+            None               => (placeholder, None)
+        };
+        let no_spec = |piece| {
+            return match (spec) {
+                Some(spec) => Err(PatternError::UnknownVariant {
+                    placeholder : String::from(name),
+                    variant     : String::from(spec),
+                    position
+                }),
+                None => Ok(piece)
+            };
+        };
+        return match (name) {
+            "time" | "time_utc" => {
+                let format = spec.unwrap_or(DEFAULT_TIME_FORMAT);
+                if (StrftimeItems::new(format).any(|item| matches!(item, Item::Error))) {
+                    return Err(PatternError::InvalidTimeFormat {
+                        format : String::from(format),
+                        position
+                    });
+                }
+                Ok(PatternPiece::Time {
+                    utc    : name == "time_utc",
+                    format : String::from(format)
+                })
+            },
+            "module"  => Ok(PatternPiece::Module(PatternVariant::parse(name, spec, position)?)),
+            "span"    => Ok(PatternPiece::Span(PatternVariant::parse(name, spec, position)?)),
+            "level"   => Ok(PatternPiece::Level(PatternVariant::parse(name, spec, position)?)),
+            "line"    => no_spec(PatternPiece::Line),
+            "column"  => no_spec(PatternPiece::Column),
+            "message" => match (spec) {
+                Some("f") => Ok(PatternPiece::Message(true)),
+                _         => no_spec(PatternPiece::Message(false))
+            },
+            "fields"  => no_spec(PatternPiece::Fields),
+            "field"   => match (spec) {
+                Some(key) if (! key.is_empty()) => Ok(PatternPiece::Field(String::from(key))),
+                _ => Err(PatternError::MissingField {position})
+            },
+            _ => Err(PatternError::UnknownPlaceholder {
+                placeholder : String::from(name),
+                position
+            })
+        };
+    }
+    /// Write the value of the piece to
+    /// the end of a line.
+    fn write(&self, context : &LogContext, line : &mut String) {
+        match (self) {
+            PatternPiece::Text(text) => line.push_str(text),
+            PatternPiece::Time {utc, format} => line.push_str(&if (*utc) {
+                context.time_utc().format(format).to_string()
+            } else {
+                context.time_local().format(format).to_string()
+            }),
+            PatternPiece::Module(variant) => line.push_str(&match (variant) {
+                PatternVariant::Plain           => context.module(),
+                PatternVariant::Padded          => context.module_p(),
+                PatternVariant::Formatted       => context.module_f(),
+                PatternVariant::PaddedFormatted => context.module_pf(),
+                PatternVariant::FormattedPadded => context.module_fp()
+            }),
+            PatternPiece::Span(variant) => line.push_str(&match (variant) {
+                PatternVariant::Plain           => context.span(),
+                PatternVariant::Padded          => context.span_p(),
+                PatternVariant::Formatted       => context.span_f(),
+                PatternVariant::PaddedFormatted => context.span_pf(),
+                PatternVariant::FormattedPadded => context.span_fp()
+            }),
+            PatternPiece::Level(variant) => line.push_str(&match (variant) {
+                PatternVariant::Plain           => context.level_name(),
+                PatternVariant::Padded          => context.level_name_p(),
+                PatternVariant::Formatted       => context.level_name_f(),
+                PatternVariant::PaddedFormatted => context.level_name_pf(),
+                PatternVariant::FormattedPadded => context.level_name_fp()
+            }),
+            PatternPiece::Line   => line.push_str(&context.line().to_string()),
+            PatternPiece::Column => line.push_str(&context.column().to_string()),
+            PatternPiece::Message(formatted) => line.push_str(&if (*formatted) {
+                context.formatted(context.message())
+            } else {
+                context.message()
+            }),
+            PatternPiece::Fields => line.push_str(&logfmt::fields(context)),
+            PatternPiece::Field(key) => if let Some(value) = context.field(key) {
+                line.push_str(&value.to_string());
+            },
+            PatternPiece::Styled {piece, styles} => {
+                let mut text = String::new();
+                piece.write(context, &mut text);
+                let text = styles.iter().fold(text.normal(), |text, style| style.apply(text));
+                line.push_str(&text.to_string());
+            }
+        }
+    }
+}
+
+/// Which of the `LogContext` functions
+/// a placeholder uses.
+#[derive(Clone, Copy, Debug)]
+enum PatternVariant {
+    Plain,
+    Padded,
+    Formatted,
+    PaddedFormatted,
+    FormattedPadded
+}
+impl PatternVariant {
+    /// Parse the variant of a placeholder.
+    fn parse(placeholder : &str, spec : Option<&str>, position : usize) -> Result<PatternVariant, PatternError> {
+        return match (spec) {
+            None       => Ok(PatternVariant::Plain),
+            Some("p")  => Ok(PatternVariant::Padded),
+            Some("f")  => Ok(PatternVariant::Formatted),
+            Some("pf") => Ok(PatternVariant::PaddedFormatted),
+            Some("fp") => Ok(PatternVariant::FormattedPadded),
+            Some(spec) => Err(PatternError::UnknownVariant {
+                placeholder : String::from(placeholder),
+                variant     : String::from(spec),
+                position
+            })
+        };
+    }
+}
+
+
+/// A colour or attribute applied to
+/// the value of a placeholder.
+#[derive(Clone, Debug)]
+enum PatternStyle {
+    Colour(Color),
+    Background(Color),
+    Bold,
+    Dimmed,
+    Italic,
+    Underline,
+    Reversed,
+    Strikethrough
+}
+impl PatternStyle {
+    /// Parse a single style.
+    fn parse(style : &str, position : usize) -> Result<PatternStyle, PatternError> {
+        let style = style.trim();
+        let parsed = match (style) {
+            "bold"          => Some(PatternStyle::Bold),
+            "dimmed"        => Some(PatternStyle::Dimmed),
+            "italic"        => Some(PatternStyle::Italic),
+            "underline"     => Some(PatternStyle::Underline),
+            "reversed"      => Some(PatternStyle::Reversed),
+            "strikethrough" => Some(PatternStyle::Strikethrough),
+            style => match (style.strip_prefix("on ")) {
+                Some(colour) => colour.trim().parse().ok().map(PatternStyle::Background),
+                None         => style.parse().ok().map(PatternStyle::Colour)
+            }
+        };
+        return parsed.ok_or_else(|| PatternError::UnknownStyle {
+            style : String::from(style),
+            position
+        });
+    }
+    /// Apply the style to some text.
+    fn apply(&self, text : ColoredString) -> ColoredString {
+        return match (self) {
+            PatternStyle::Colour(colour)     => text.color(*colour),
+            PatternStyle::Background(colour) => text.on_color(*colour),
+            PatternStyle::Bold               => text.bold(),
+            PatternStyle::Dimmed             => text.dimmed(),
+            PatternStyle::Italic             => text.italic(),
+            PatternStyle::Underline          => text.underline(),
+            PatternStyle::Reversed           => text.reversed(),
+            PatternStyle::Strikethrough      => text.strikethrough()
+        };
+    }
+}
+
+
+
+/// A problem found while parsing a `Pattern`.
+/// 
+/// Each position is the byte index of the
+/// problem in the template string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PatternError {
+    /// A placeholder name that does not exist.
+    UnknownPlaceholder {
+        placeholder : String,
+        position    : usize
+    },
+    /// A variant that the placeholder does
+    /// not support.
+    UnknownVariant {
+        placeholder : String,
+        variant     : String,
+        position    : usize
+    },
+    /// An invalid `chrono` format string.
+    InvalidTimeFormat {
+        format   : String,
+        position : usize
+    },
+    /// A colour or attribute that does
+    /// not exist.
+    UnknownStyle {
+        style    : String,
+        position : usize
+    },
+    /// A `{field}` placeholder without the
+    /// name of the field.
+    MissingField {
+        position : usize
+    },
+    /// A `{` without a matching `}`.
+    Unclosed {
+        position : usize
+    },
+    /// A `}` without a matching `{`.
+    Unopened {
+        position : usize
+    }
+}
+impl fmt::Display for PatternError {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        return match (self) {
+            PatternError::UnknownPlaceholder {placeholder, position} => write!(f,
+                "Unknown placeholder `{{{}}}` at position {}. Expected one of `time`, `time_utc`, `module`, `span`, `level`, `line`, `column`, `message`, `fields` or `field`.",
+                placeholder, position
+            ),
+            PatternError::UnknownVariant {placeholder, variant, position} => write!(f,
+                "Unknown variant `{}` for placeholder `{{{}}}` at position {}.",
+                variant, placeholder, position
+            ),
+            PatternError::InvalidTimeFormat {format, position} => write!(f,
+                "Invalid time format `{}` at position {}.",
+                format, position
+            ),
+            PatternError::UnknownStyle {style, position} => write!(f,
+                "Unknown style `{}` at position {}. Expected a colour, `on` and a colour, `bold`, `dimmed`, `italic`, `underline`, `reversed` or `strikethrough`.",
+                style, position
+            ),
+            PatternError::MissingField {position} => write!(f,
+                "Missing field name for placeholder `{{field}}` at position {}. Use `{{field:NAME}}`.",
+                position
+            ),
+            PatternError::Unclosed {position} => write!(f,
+                "Unclosed `{{` at position {}. Use `{{{{` for a literal `{{`.",
+                position
+            ),
+            PatternError::Unopened {position} => write!(f,
+                "Unmatched `}}` at position {}. Use `}}}}` for a literal `}}`.",
+                position
+            )
+        };
+    }
+}
+impl Error for PatternError {}
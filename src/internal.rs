@@ -8,10 +8,14 @@
 
 
 use std::collections::HashMap;
+use std::sync::{Arc, Weak};
+use std::sync::atomic::{AtomicU8, AtomicUsize, Ordering};
 
+use arc_swap::ArcSwapOption;
 use static_init::dynamic;
 
 use crate::Logger;
+use crate::logger::UnregisteredPolicy;
 
 
 
@@ -23,8 +27,35 @@ pub enum LoggerLocation {
     /// Use a logger defined for the module.
     Here(Logger)
 }
-/// Thread safety.
-unsafe impl Send for LoggerLocation {}
+impl From<Logger> for LoggerLocation {
+    fn from(logger : Logger) -> LoggerLocation {
+        return LoggerLocation::Here(logger);
+    }
+}
+
+/// An object representing which logger
+/// is used in a registered module.
+/// 
+/// Callsites and messages being logged
+/// keep a reference to the logger, so a
+/// replaced logger is only dropped, and
+/// its targets closed, once they are done
+/// with it.
+#[derive(Clone)]
+pub enum RegisteredLogger {
+    /// Use the logger in the parent module.
+    Super,
+    /// Use a logger defined for the module.
+    Here(Arc<Logger>)
+}
+impl From<LoggerLocation> for RegisteredLogger {
+    fn from(location : LoggerLocation) -> RegisteredLogger {
+        return match (location) {
+            LoggerLocation::Super        => RegisteredLogger::Super,
+            LoggerLocation::Here(logger) => RegisteredLogger::Here(Arc::new(logger))
+        };
+    }
+}
 
 
 /// An object that stores loggers for
@@ -32,31 +63,343 @@ unsafe impl Send for LoggerLocation {}
 /// 
 /// The key is the module path.
 /// 
-/// The value is the LoggerLocation pointing
+/// The value is the RegisteredLogger pointing
 /// to the logger for the module path.
 /// 
 /// Contains a default logger at the root.
+/// 
+/// Use `register` to add loggers, so
+/// that callsites notice the change.
 #[dynamic]
-pub static mut LOGGERS : HashMap<String, LoggerLocation> = {
+pub static mut LOGGERS : HashMap<String, RegisteredLogger> = {
     let mut map = HashMap::new();
-    map.insert(String::new(), LoggerLocation::Here(Logger::default()));
+    map.insert(String::new(), RegisteredLogger::from(LoggerLocation::Here(Logger::default())));
     map
 };
 
+/// Loggers that have been replaced, but
+/// may still be used by callsites or
+/// messages being logged.
+/// 
+/// Walked by `loggerithm::flush` and
+/// `loggerithm::shutdown`, so that they
+/// do not lose buffered messages.
+#[dynamic]
+static mut RETIRED : Vec<Weak<Logger>> = Vec::new();
+
+/// An object that stores the minimum
+/// severity set at runtime for modules.
+/// 
+/// The key is the module path.
+/// 
+/// The value overrides the minimum severity
+/// of the logger for messages logged in the
+/// module and its submodules.
+/// 
+/// Use `set_severity_override` to change
+/// it, so that callsites notice the change.
+#[dynamic]
+pub static mut SEVERITY_OVERRIDES : HashMap<String, u32> = HashMap::new();
+
+/// An object that stores the severity of
+/// each registered logging level.
+/// 
+/// The key is the level name in uppercase.
+/// 
+/// Used to find levels by name, such as
+/// in `env` directives.
+#[dynamic]
+pub static mut LEVELS : HashMap<String, u32> = HashMap::new();
+
+/// What happens when a module without a
+/// registered logger logs a message.
+/// 
+/// Stores an `UnregisteredPolicy`.
+static UNREGISTERED_POLICY : AtomicU8 = AtomicU8::new(UnregisteredPolicy::Root as u8);
+
+/// Increased every time `LOGGERS`,
+/// `SEVERITY_OVERRIDES` or the
+/// `UnregisteredPolicy` changes.
+/// 
+/// Used by callsites to check if the
+/// logger that they found is out of date.
+pub static GENERATION : AtomicUsize = AtomicUsize::new(0);
+
 /// The length of the largest logging
 /// level name.
 /// 
 /// Used in order to pad the level names
 /// to a consistent value.
-pub static mut MAX_LEVEL_NAME_LEN : usize = 0;
+pub static MAX_LEVEL_NAME_LEN : AtomicUsize = AtomicUsize::new(0);
 
 /// The length of the largest module path.
 /// 
 /// Used in order to pad the module paths
 /// to a consistent value.
-pub static mut MAX_MODULE_LEN : usize = 0;
+pub static MAX_MODULE_LEN : AtomicUsize = AtomicUsize::new(0);
+
+/// The length of the largest span path.
+/// 
+/// Used in order to pad the span paths
+/// to a consistent value.
+pub static MAX_SPAN_LEN : AtomicUsize = AtomicUsize::new(0);
+
 
 
+/// Register the logger for a module.
+/// 
+/// # Arguments
+/// 
+/// * `module`   - The module path.
+/// * `location` - The logger to use in the module.
+pub fn register(module : String, location : LoggerLocation) {
+    let replaced = LOGGERS.write().insert(module, RegisteredLogger::from(location));
+    GENERATION.fetch_add(1, Ordering::AcqRel);
+    retire(replaced);
+}
+
+/// Replace the loggers and minimum severity
+/// overrides of modules at the same time.
+/// 
+/// Each replaced logger is flushed, then
+/// closed once no messages are being
+/// logged by it on other threads, and no
+/// callsites are still using it. Until
+/// then, `loggerithm::flush` and
+/// `loggerithm::shutdown` still reach it.
+/// 
+/// # Arguments
+/// 
+/// * `loggers`    - The module paths, and the loggers
+///                  to use in them, which may already
+///                  be registered for other modules.
+/// * `severities` - The module paths, and the new
+///                  minimum severity overrides, or
+///                  `None` to remove them.
+pub fn reload(loggers : Vec<(String, RegisteredLogger)>, severities : Vec<(String, Option<u32>)>) {
+    let mut replaced = Vec::new();
+    {
+        let mut registered = LOGGERS.write();
+        let mut overrides  = SEVERITY_OVERRIDES.write();
+        for (module, location) in loggers {
+            replaced.extend(registered.insert(module, location));
+        }
+        for (module, min_severity) in severities {
+            match (min_severity) {
+                Some(min_severity) => {overrides.insert(module, min_severity);},
+                None               => {overrides.remove(&module);}
+            }
+        }
+        GENERATION.fetch_add(1, Ordering::AcqRel);
+    }
+    // Retired after the locks are released,
+    // as flushing the targets may be slow.
+    for logger in replaced {
+        retire(Some(logger));
+    }
+}
+
+/// Flush a logger that has been replaced,
+/// and keep track of it until it is
+/// dropped.
+fn retire(replaced : Option<RegisteredLogger>) {
+    if let Some(RegisteredLogger::Here(logger)) = replaced {
+        logger.flush();
+        let mut retired = RETIRED.write();
+        retired.retain(|logger| logger.strong_count() > 0);
+        retired.push(Arc::downgrade(&logger));
+    }
+}
+
+/// Get every logger defined for a module,
+/// in order of their module paths.
+/// 
+/// Loggers that have been replaced are
+/// not included.
+pub fn registered_loggers() -> Vec<Arc<Logger>> {
+    let registered  = LOGGERS.read();
+    let mut loggers = registered.iter()
+        .filter_map(|(module, location)| match (location) {
+            RegisteredLogger::Here(logger) => Some((module, Arc::clone(logger))),
+            RegisteredLogger::Super        => None
+        })
+        .collect::<Vec<_>>();
+    loggers.sort_by_key(|(module, _)| *module);
+    return loggers.into_iter().map(|(_, logger)| logger).collect();
+}
+
+/// Get every logger that has been
+/// replaced, but is still used somewhere,
+/// in the order that they were replaced.
+pub fn retired_loggers() -> Vec<Arc<Logger>> {
+    return RETIRED.read().iter().filter_map(Weak::upgrade).collect();
+}
+
+
+/// Register the name of a logging level.
+/// 
+/// # Arguments
+/// 
+/// * `name`     - The name of the level.
+/// * `severity` - The severity of the level.
+pub fn register_level(name : &str, severity : u32) {
+    LEVELS.write().insert(name.to_uppercase(), severity);
+}
+
+/// Gets the severity of a registered
+/// logging level.
+/// 
+/// # Arguments
+/// 
+/// * `name` - The name of the level. Case
+///            is ignored.
+/// 
+/// # Returns
+/// 
+/// The severity, or `None` if no level
+/// has the name.
+pub fn find_level(name : &str) -> Option<u32> {
+    return LEVELS.read().get(&name.to_uppercase()).copied();
+}
+
+
+/// Parse the name of a minimum severity.
+/// 
+/// # Arguments
+/// 
+/// * `level` - The name of a registered level,
+///             ignoring case, a severity number,
+///             or `off`.
+/// 
+/// # Returns
+/// 
+/// The severity, or `None` if the name
+/// is not valid.
+pub fn parse_level(level : &str) -> Option<u32> {
+    if (level.eq_ignore_ascii_case("off")) {
+        return Some(u32::MAX);
+    }
+    if let Ok(severity) = level.parse::<u32>() {
+        return Some(severity);
+    }
+    return find_level(level);
+}
+
+
+/// Set or remove the minimum severity
+/// override of a module.
+/// 
+/// # Arguments
+/// 
+/// * `module`       - The module path.
+/// * `min_severity` - The new minimum severity, or
+///                    `None` to remove the override.
+pub fn set_severity_override(module : &str, min_severity : Option<u32>) {
+    let mut overrides = SEVERITY_OVERRIDES.write();
+    match (min_severity) {
+        Some(min_severity) => {overrides.insert(String::from(module), min_severity);},
+        None               => {overrides.remove(module);}
+    }
+    GENERATION.fetch_add(1, Ordering::AcqRel);
+}
+
+/// Gets the minimum severity override
+/// of a module.
+/// 
+/// # Arguments
+/// 
+/// * `module` - The module to get the override
+///              of.
+/// 
+/// # Returns
+/// 
+/// The override of the closest module
+/// up to the root that has one, or `None`.
+pub fn find_severity_override(module : &str) -> Option<u32> {
+    let overrides = SEVERITY_OVERRIDES.read();
+    if (overrides.is_empty()) {
+        return None;
+    }
+    let mut module = module;
+    loop {
+        if let Some(min_severity) = overrides.get(module) {
+            return Some(*min_severity);
+        }
+        if (module.is_empty()) {
+            return None;
+        }
+        module = match (module.rfind("::")) {
+            Some(index) => &module[..index],
+            None        => ""
+        };
+    }
+}
+
+
+/// Set what happens when a module without
+/// a registered logger logs a message.
+/// 
+/// # Arguments
+/// 
+/// * `policy` - The new policy.
+pub fn set_unregistered_policy(policy : UnregisteredPolicy) {
+    UNREGISTERED_POLICY.store(policy as u8, Ordering::Release);
+    GENERATION.fetch_add(1, Ordering::AcqRel);
+}
+
+/// Get what happens when a module without
+/// a registered logger logs a message.
+pub fn unregistered_policy() -> UnregisteredPolicy {
+    return match (UNREGISTERED_POLICY.load(Ordering::Acquire)) {
+        policy if (policy == UnregisteredPolicy::Drop as u8)  => UnregisteredPolicy::Drop,
+        policy if (policy == UnregisteredPolicy::Panic as u8) => UnregisteredPolicy::Panic,
+        _                                                      => UnregisteredPolicy::Root
+    };
+}
+
+
+/// Gets the logger of a module.
+/// 
+/// # Arguments
+/// 
+/// * `module` - The module to get the logger
+///              of.
+/// * `first`  - If `true`, follow the
+///              `UnregisteredPolicy` if the module
+///              itself is not registered.
+/// 
+/// # Returns
+/// 
+/// The logger, or `None` if no module
+/// up to the root has one.
+pub fn find_module_logger(module : &str, first : bool) -> Option<Arc<Logger>> {
+    let loggers    = LOGGERS.read();
+    let mut module = module;
+    let mut first  = first;
+    loop {
+        match (loggers.get(module)) {
+            Some(RegisteredLogger::Here(logger)) => {
+                return Some(Arc::clone(logger));
+            },
+            Some(RegisteredLogger::Super) => {},
+            None => if (first) {
+                match (unregistered_policy()) {
+                    UnregisteredPolicy::Root  => {},
+                    UnregisteredPolicy::Drop  => return None,
+                    UnregisteredPolicy::Panic => panic!("Logger for module `{}` not registered.", module)
+                }
+            }
+        }
+        if (module.is_empty()) {
+            return None;
+        }
+        module = match (module.rfind("::")) {
+            Some(index) => &module[..index],
+            None        => ""
+        };
+        first = false;
+    }
+}
 
 /// Gets the logger of a module, then
 /// calls the callback function.
@@ -66,6 +409,7 @@ pub static mut MAX_MODULE_LEN : usize = 0;
 /// * `module`   - The module to get the logger
 ///                of.
 /// * `first`    - Always pass in `true`.
+///                See `find_module_logger`.
 /// * `callback` - A callback function that will
 ///                be run when a logger is found.
 /// 
@@ -75,28 +419,64 @@ pub static mut MAX_MODULE_LEN : usize = 0;
 pub fn run_module_logger<F>(module : String, first : bool, callback : F)
     where F : Fn(&Logger)
 {
-    let mut next_module_vec = module.split("::").collect::<Vec<&str>>();
-    next_module_vec.remove(next_module_vec.len() - 1);
-    let next_module = next_module_vec.join("::");
-
-    match (LOGGERS.read().get(&module)) {
-        Some(location) => {
-            match (location) {
-                LoggerLocation::Super => {
-                    run_module_logger(next_module, false, callback);
-                },
-                LoggerLocation::Here(logger) => {
-                    callback(&logger);
-                }
-            }
-        },
-        None => {
-            if (first) {
-                panic!("Logger for module `{}` not registered.", module);
-            } else {
-                run_module_logger(next_module, false, callback);
+    if let Some(logger) = find_module_logger(&module, first) {
+        callback(&logger);
+    }
+}
+
+
+/// The logger found by a `log!` callsite.
+struct Resolved {
+    generation   : usize,
+    logger       : Arc<Logger>,
+    min_severity : u32
+}
+
+/// A cache of the logger used by a
+/// single `log!` callsite.
+/// 
+/// Once the logger has been found, later
+/// calls only need to check that `LOGGERS`
+/// and `SEVERITY_OVERRIDES` have not
+/// changed.
+pub struct Callsite {
+    resolved : ArcSwapOption<Resolved>
+}
+impl Callsite {
+    /// Create an empty cache.
+    pub const fn new() -> Callsite {
+        return Callsite {
+            resolved : ArcSwapOption::const_empty()
+        };
+    }
+    /// Get the logger of a module, if a
+    /// message at a severity would be
+    /// logged by it.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `module`   - The module that the callsite
+    ///                is in.
+    /// * `severity` - The severity of the message.
+    pub fn logger(&self, module : &str, severity : u32) -> Option<Arc<Logger>> {
+        let generation = GENERATION.load(Ordering::Acquire);
+        let resolved   = self.resolved.load();
+        if let Some(resolved) = &*resolved {
+            if (resolved.generation == generation) {
+                return (severity >= resolved.min_severity).then(|| Arc::clone(&resolved.logger));
             }
         }
+        let logger   = find_module_logger(module, true)?;
+        let resolved = Resolved {
+            generation,
+            min_severity : logger.effective_min_severity(module),
+            logger
+        };
+        let logged = (severity >= resolved.min_severity).then(|| Arc::clone(&resolved.logger));
+        // The old value is freed once no other
+        // thread is reading it.
+        self.resolved.store(Some(Arc::new(resolved)));
+        return logged;
     }
 }
 
@@ -120,13 +500,8 @@ macro_rules! __logger_internal {
                 let mut module_vec = module_path!().split("::").collect::<Vec<&str>>();
                 module_vec.remove(module_vec.len() - 1);
                 let module = module_vec.join("::");
-                if (unsafe {$crate::internal::MAX_MODULE_LEN} < module.len()) {
-                    unsafe {
-                        $crate::internal::MAX_MODULE_LEN = module.len();
-                    }
-                }
-                unsafe {$crate::internal::LOGGERS.write()}
-                    .insert(module, $location);
+                $crate::internal::MAX_MODULE_LEN.fetch_max(module.len(), std::sync::atomic::Ordering::Relaxed);
+                $crate::internal::register(module, $location);
             };
             /// Used by the `log!` macro to
             /// check if there is a logger
@@ -3,7 +3,10 @@
 //! # Examples
 // 
 //! ```
+//! # use loggerithm::{logger, log};
 //! use loggerithm::level::{TRACE, DEBUG, INFO, NOTICE, SUCCESS, FAILURE, WARN, ERROR, FATAL};
+//! # logger!(super);
+//! # fn main() {
 //! log!(TRACE, "Test message");
 //! log!(DEBUG, "Test message");
 //! log!(INFO, "Test message");
@@ -13,10 +16,14 @@
 //! log!(WARN, "Test message");
 //! log!(ERROR, "Test message");
 //! log!(FATAL, "Test message");
+//! # }
 //! ```
 
 
 
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+
 use colored::{ColoredString, Colorize};
 
 use crate::internal;
@@ -26,10 +33,11 @@ use crate::level;
 
 /// An object containing information
 /// about how to print a log message.
+#[derive(Clone)]
 pub struct LogLevel {
     name      : String,
     severity  : u32,
-    formatter : Box<dyn Fn(String) -> ColoredString>,
+    formatter : Arc<dyn Fn(String) -> ColoredString + Send + Sync>,
     init      : bool
 }
 /// Initialisation.
@@ -51,13 +59,16 @@ impl LogLevel {
     /// # Examples
     /// 
     /// ```
+    /// # use loggerithm::log_level;
+    /// # use loggerithm::level::LogLevel;
     /// log_level!(ALERT, LogLevel::new(30));
+    /// # fn main() {}
     /// ```
     pub fn new(severity : u32) -> LogLevel {
         let level = LogLevel {
             name      : String::new(),
             severity,
-            formatter : Box::new(|v| v.normal()),
+            formatter : Arc::new(|v| v.normal()),
             init      : false
         };
         return level;
@@ -84,11 +95,8 @@ impl LogLevel {
         }
         self.init = true;
         self.name = String::from(name);
-        if (unsafe {internal::MAX_LEVEL_NAME_LEN} < self.name.len()) {
-            unsafe {
-                internal::MAX_LEVEL_NAME_LEN = self.name.len();
-            }
-        }
+        internal::register_level(name, self.severity);
+        internal::MAX_LEVEL_NAME_LEN.fetch_max(self.name.len(), Ordering::Relaxed);
         return self;
     }
     /// Set the formatting of the log level.
@@ -108,9 +116,9 @@ impl LogLevel {
     /// 
     /// `self`
     pub fn formatted<F : 'static>(mut self, formatter : F) -> LogLevel
-        where F : Fn(String) -> ColoredString
+        where F : Fn(String) -> ColoredString + Send + Sync
     {
-        self.formatter = Box::new(formatter);
+        self.formatter = Arc::new(formatter);
         return self;
     }
 }
@@ -145,8 +153,62 @@ impl LogLevel {
         return (self.formatter)(text).to_string();
     }
 }
-/// Thread safety.
-unsafe impl Sync for LogLevel {}
+
+
+
+/// The lowest severity that `log!` calls
+/// are compiled in for.
+/// 
+/// Set using the `max_level_*` and
+/// `release_max_level_*` features. `log!`
+/// calls below it, using a level with a
+/// static severity, compile to nothing.
+/// 
+/// * `*_off`   - Nothing is compiled in.
+/// * `*_error` - `ERROR` and above.
+/// * `*_warn`  - `WARN` and above.
+/// * `*_info`  - `INFO` and above.
+/// * `*_debug` - `DEBUG` and above.
+/// * `*_trace` - Everything.
+/// 
+/// The `release_max_level_*` features are
+/// only used when debug assertions are off.
+pub const STATIC_MIN_SEVERITY : u32 = static_min_severity();
+
+/// Find the lowest severity that is
+/// compiled in, based on the enabled
+/// features.
+const fn static_min_severity() -> u32 {
+    if (! cfg!(debug_assertions)) {
+        if (cfg!(feature = "release_max_level_off"))   {return u32::MAX;}
+        if (cfg!(feature = "release_max_level_error")) {return 40;}
+        if (cfg!(feature = "release_max_level_warn"))  {return 30;}
+        if (cfg!(feature = "release_max_level_info"))  {return 20;}
+        if (cfg!(feature = "release_max_level_debug")) {return 10;}
+        if (cfg!(feature = "release_max_level_trace")) {return 0;}
+    }
+    if (cfg!(feature = "max_level_off"))   {return u32::MAX;}
+    if (cfg!(feature = "max_level_error")) {return 40;}
+    if (cfg!(feature = "max_level_warn"))  {return 30;}
+    if (cfg!(feature = "max_level_info"))  {return 20;}
+    if (cfg!(feature = "max_level_debug")) {return 10;}
+    return 0;
+}
+
+/// Check if a level is compiled in.
+/// 
+/// # Arguments
+/// 
+/// * `static_severity` - The `STATIC_SEVERITY` of
+///                       the level.
+// `STATIC_MIN_SEVERITY` is `0` unless a feature is enabled.
+#[allow(clippy::absurd_extreme_comparisons)]
+pub const fn statically_enabled(static_severity : Option<u32>) -> bool {
+    return match (static_severity) {
+        Some(severity) => severity >= STATIC_MIN_SEVERITY,
+        None           => true
+    };
+}
 
 
 
@@ -154,10 +216,15 @@ unsafe impl Sync for LogLevel {}
 /// 
 /// # Arguments
 /// 
-/// * `name`   - The identifier to store the level at.
-///              This will be the name of the level
-///              and the name used to access it.
-/// * `logger` - The level to register and store.
+/// * `name`     - The identifier to store the level at.
+///                This will be the name of the level
+///                and the name used to access it.
+/// * `severity` - Optional. The severity of the level,
+///                after `static`. It must match the
+///                severity of `level`, and allows
+///                `log!` calls to be removed at compile
+///                time. See `STATIC_MIN_SEVERITY`.
+/// * `level`    - The level to register and store.
 /// 
 /// # Returns
 /// 
@@ -167,14 +234,26 @@ unsafe impl Sync for LogLevel {}
 /// # Examples
 /// 
 /// ```
+/// # use loggerithm::{logger, log, log_level};
+/// # use loggerithm::level::LogLevel;
+/// # logger!(super);
 /// log_level!(ALERT, LogLevel::new(30));
 /// fn main() {
 ///     log!(ALERT, "This is an alert!");
 /// }
 /// ```
+/// ```
+/// # use loggerithm::{logger, log, log_level};
+/// # use loggerithm::level::LogLevel;
+/// # logger!(super);
+/// log_level!(VERBOSE, static 7, LogLevel::new(7));
+/// fn main() {
+///     log!(VERBOSE, "This is removed when `max_level_debug` is enabled.");
+/// }
+/// ```
 #[macro_export]
 macro_rules! log_level {
-    ($name:ident, $level:expr) => {
+    (@ $name:ident, $static_severity:expr, $level:expr) => {
         /// A logging level.
         #[allow(non_snake_case)]
         pub mod $name {
@@ -182,7 +261,18 @@ macro_rules! log_level {
             extern crate static_init;
             /// The log level object.
             #[static_init::dynamic]
-            pub static LEVEL : $crate::level::LogLevel = $level.init(stringify!($name));
+            pub static LEVEL : $crate::level::LogLevel = {
+                let level = $level.init(stringify!($name));
+                if let Some(severity) = STATIC_SEVERITY {
+                    assert_eq!(level.get_severity(), severity,
+                        "Static severity of level `{}` does not match its severity.", stringify!($name)
+                    );
+                }
+                level
+            };
+            /// The severity of the level, if it
+            /// is known at compile time.
+            pub const STATIC_SEVERITY : Option<u32> = $static_severity;
             /// Get the severity value of the log
             /// level.
             /// 
@@ -193,44 +283,53 @@ macro_rules! log_level {
             /// # Examples
             /// 
             /// ```
+            /// # use loggerithm::logger;
+            /// # use loggerithm::logger::Logger;
+            /// # use loggerithm::level::WARN;
             /// logger!(Logger::new()
             ///     .set_min_severity(WARN::SEVERITY)
             /// );
+            /// # fn main() {}
             /// ```
             pub fn SEVERITY() -> u32 {
                 return LEVEL.get_severity();
             }
         }
     };
+    ($name:ident, static $severity:expr, $level:expr) => {
+        $crate::log_level!(@ $name, Some($severity), $level);
+    };
+    ($name:ident, $level:expr) => {
+        $crate::log_level!(@ $name, None, $level);
+    };
 }
-pub(crate) use log_level;
 
 
 
-log_level!(TRACE, level::LogLevel::new(5)
+log_level!(TRACE, static 5, level::LogLevel::new(5)
     .formatted(|v| v.bright_black())
 );
-log_level!(DEBUG, level::LogLevel::new(10)
+log_level!(DEBUG, static 10, level::LogLevel::new(10)
     .formatted(|v| v.white().dimmed())
 );
-log_level!(INFO, level::LogLevel::new(20)
+log_level!(INFO, static 20, level::LogLevel::new(20)
     .formatted(|v| v.cyan().dimmed())
 );
-log_level!(NOTICE, level::LogLevel::new(25)
+log_level!(NOTICE, static 25, level::LogLevel::new(25)
     .formatted(|v| v.bright_cyan())
 );
-log_level!(SUCCESS, level::LogLevel::new(25)
+log_level!(SUCCESS, static 25, level::LogLevel::new(25)
     .formatted(|v| v.green())
 );
-log_level!(WARN, level::LogLevel::new(30)
+log_level!(WARN, static 30, level::LogLevel::new(30)
     .formatted(|v| v.yellow())
 );
-log_level!(FAILURE, level::LogLevel::new(35)
+log_level!(FAILURE, static 35, level::LogLevel::new(35)
     .formatted(|v| v.red())
 );
-log_level!(ERROR, level::LogLevel::new(40)
+log_level!(ERROR, static 40, level::LogLevel::new(40)
     .formatted(|v| v.bright_red().bold())
 );
-log_level!(FATAL, level::LogLevel::new(50)
+log_level!(FATAL, static 50, level::LogLevel::new(50)
     .formatted(|v| v.bright_white().bold().on_red())
 );
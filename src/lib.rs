@@ -4,31 +4,52 @@
 //! 
 //! Docs.rs    : [https://docs.rs/loggerithm](https://docs.rs/loggerithm)
 #![allow(unused_parens)]
+#![allow(
+    clippy::needless_return,
+    clippy::redundant_field_names,
+    clippy::multiple_bound_locations,
+    clippy::new_without_default,
+    clippy::should_implement_trait,
+    clippy::doc_overindented_list_items
+)]
 
 
 
+use std::sync::atomic::Ordering;
+
 use colored::Colorize;
-use chrono;
 use chrono::DateTime;
 
+pub mod bridge;
+#[cfg(feature = "config")]
+pub mod config;
+pub mod env;
+pub mod field;
+pub mod filter;
+pub mod format;
 pub mod internal;
 pub mod level;
 pub mod logger;
+pub mod scope;
+pub mod span;
+pub mod target;
 use logger::Logger;
 use level::LogLevel;
+use field::Value;
+pub use scope::scope;
 
 
 
 /// Passed as an argument when the log
 /// target callback is called.
 pub struct LogContext<'l> {
-    #[allow(dead_code)]
-    logger   : &'l Logger,
     time     : DateTime<chrono::Utc>,
     module   : String,
     position : (u32, u32),
     level    : &'l LogLevel,
-    text     : String
+    text     : String,
+    spans    : Vec<String>,
+    fields   : Vec<(String, Value)>
 }
 impl LogContext<'_> {
     /// Get the current UTC time.
@@ -59,6 +80,32 @@ impl LogContext<'_> {
     pub fn module_fp(&self) -> String {
         return self.padded_module(self.module_f(), self.module().len());
     }
+    /// Get the path of the spans that the log command was run in,
+    /// separated by `::`. Empty if not in a span.
+    pub fn span(&self) -> String {
+        return self.spans.join("::");
+    }
+    /// Get the span path that has been padded.
+    pub fn span_p(&self) -> String {
+        return self.padded_span(self.span(), self.span().len());
+    }
+    /// Get the span path that has been formatted.
+    pub fn span_f(&self) -> String {
+        return self.formatted(self.span());
+    }
+    /// Get the span path that has been padded then formatted.
+    pub fn span_pf(&self) -> String {
+        return self.formatted(self.span_p());
+    }
+    /// Get the span path that has been formatted then padded.
+    pub fn span_fp(&self) -> String {
+        return self.padded_span(self.span_f(), self.span().len());
+    }
+    /// Get the names of the spans that the log command was
+    /// run in, from outermost to innermost.
+    pub fn spans(&self) -> impl Iterator<Item = &str> {
+        return self.spans.iter().map(|name| name.as_str());
+    }
     /// Get the line number that the log command was run at.
     pub fn line(&self) -> u32 {
         return self.position.0;
@@ -95,6 +142,69 @@ impl LogContext<'_> {
     pub fn message(&self) -> String {
         return String::from(&self.text);
     }
+    /// Get the structured fields passed into
+    /// the log command, in order.
+    pub fn fields(&self) -> impl Iterator<Item = (&str, &Value)> {
+        return self.fields.iter().map(|(key, value)| (key.as_str(), value));
+    }
+    /// Get the value of a structured field.
+    pub fn field(&self, key : &str) -> Option<&Value> {
+        return self.fields.iter().find(|(k, _)| k == key).map(|(_, value)| value);
+    }
+}
+impl LogContext<'_> {
+    /// Set the time that the message was
+    /// logged at.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `time` - The UTC time.
+    pub fn set_time(&mut self, time : DateTime<chrono::Utc>) {
+        self.time = time;
+    }
+    /// Add a structured field.
+    /// 
+    /// If there is already a field with the
+    /// same key, its value is replaced.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `key`   - The name of the field.
+    /// * `value` - The value of the field.
+    pub fn add_field<K, V>(&mut self, key : K, value : V)
+        where K : Into<String>, V : Into<Value>
+    {
+        let key   = key.into();
+        let value = value.into();
+        match (self.fields.iter_mut().find(|(k, _)| *k == key)) {
+            Some((_, existing)) => *existing = value,
+            None                => self.fields.push((key, value))
+        }
+    }
+}
+impl LogContext<'_> {
+    /// Copy the log message, so that it
+    /// can be sent to another thread.
+    pub(crate) fn to_owned_context(&self) -> OwnedLogContext {
+        return OwnedLogContext {
+            time     : self.time,
+            module   : self.module.clone(),
+            position : self.position,
+            level    : self.level.clone(),
+            text     : self.text.clone(),
+            spans    : self.spans.clone(),
+            fields   : self.fields.clone()
+        };
+    }
+    /// Add spans that the message was
+    /// logged in, inside the current ones.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn push_spans<I>(&mut self, spans : I)
+        where I : IntoIterator<Item = String>
+    {
+        self.spans.extend(spans);
+        internal::MAX_SPAN_LEN.fetch_max(self.span().len(), Ordering::Relaxed);
+    }
 }
 impl LogContext<'_> {
     /// Format text based on the logging level passed into the log command.
@@ -102,10 +212,13 @@ impl LogContext<'_> {
         return self.level.format(text);
     }
     fn padded_level_name(&self, text : String, len : usize) -> String {
-        return self.padded(text, len, unsafe {internal::MAX_LEVEL_NAME_LEN});
+        return self.padded(text, len, internal::MAX_LEVEL_NAME_LEN.load(Ordering::Relaxed));
     }
     fn padded_module(&self, text : String, len : usize) -> String {
-        return self.padded(text, len, unsafe {internal::MAX_MODULE_LEN});
+        return self.padded(text, len, internal::MAX_MODULE_LEN.load(Ordering::Relaxed));
+    }
+    fn padded_span(&self, text : String, len : usize) -> String {
+        return self.padded(text, len, internal::MAX_SPAN_LEN.load(Ordering::Relaxed));
     }
     fn padded(&self, text : String, len : usize, target_len : usize) -> String {
         return format!("{:01$}", text, target_len + (text.len() - len));
@@ -114,6 +227,244 @@ impl LogContext<'_> {
 
 
 
+/// An owned copy of a `LogContext`.
+pub(crate) struct OwnedLogContext {
+    time     : DateTime<chrono::Utc>,
+    module   : String,
+    position : (u32, u32),
+    level    : LogLevel,
+    text     : String,
+    spans    : Vec<String>,
+    fields   : Vec<(String, Value)>
+}
+impl OwnedLogContext {
+    /// Create a new log message, which is
+    /// not logged by any logger.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `level`  - The level of the message.
+    /// * `module` - The module that the message
+    ///              is from.
+    /// * `text`   - The message.
+    pub(crate) fn new(level : &LogLevel, module : &str, text : String) -> OwnedLogContext {
+        return OwnedLogContext {
+            time     : chrono::Utc::now(),
+            module   : String::from(module),
+            position : (0, 0),
+            level    : level.clone(),
+            text,
+            spans    : Vec::new(),
+            fields   : Vec::new()
+        };
+    }
+    /// Borrow the log message as a `LogContext`.
+    pub(crate) fn context(&self) -> LogContext<'_> {
+        return LogContext {
+            time     : self.time,
+            module   : self.module.clone(),
+            position : self.position,
+            level    : &self.level,
+            text     : self.text.clone(),
+            spans    : self.spans.clone(),
+            fields   : self.fields.clone()
+        };
+    }
+}
+
+
+
+/// Set the minimum severity index required
+/// for a message logged in a module, or
+/// any of its submodules, to be logged.
+/// 
+/// This overrides the minimum severity of
+/// the module's logger, and takes effect
+/// immediately. Submodules with their own
+/// override use that instead.
+/// 
+/// # Arguments
+/// 
+/// * `module`       - The module path, such as
+///                    `"app::db"`. `""` applies to
+///                    every module.
+/// * `min_severity` - The minimum severity.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use loggerithm::{logger, log};
+/// # use loggerithm::level::DEBUG;
+/// # logger!(super);
+/// # fn main() {
+/// loggerithm::set_module_min_severity(module_path!(), DEBUG::SEVERITY);
+/// log!(DEBUG, "This is now logged.");
+/// loggerithm::clear_module_min_severity(module_path!());
+/// log!(DEBUG, "This is not logged again.");
+/// # }
+/// ```
+pub fn set_module_min_severity<F>(module : &str, min_severity : F)
+    where F : Fn() -> u32
+{
+    internal::set_severity_override(module, Some(min_severity()));
+}
+
+/// Remove the minimum severity set with
+/// `set_module_min_severity` for a module.
+/// 
+/// Overrides of its parent modules apply
+/// to it again.
+/// 
+/// # Arguments
+/// 
+/// * `module` - The module path.
+pub fn clear_module_min_severity(module : &str) {
+    internal::set_severity_override(module, None);
+}
+
+
+
+/// Replace the loggers of modules while
+/// the program is running.
+/// 
+/// Every logger is replaced at the same
+/// time, and takes effect immediately.
+/// Any messages being logged by the
+/// replaced loggers on other threads are
+/// still written, then the replaced
+/// loggers are closed.
+/// 
+/// # Arguments
+/// 
+/// * `loggers` - The module paths, and the loggers to
+///               use in them. `""` is the root. Use
+///               `internal::LoggerLocation::Super` to
+///               use the logger of the parent module.
+/// 
+/// # Generics
+/// 
+/// * `M` - The module path.
+/// * `L` - A `Logger` or `internal::LoggerLocation`.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use loggerithm::{logger, log};
+/// # use loggerithm::logger::Logger;
+/// # use loggerithm::level::DEBUG;
+/// # logger!(super);
+/// # fn main() {
+/// loggerithm::reload([(module_path!(), Logger::new()
+///     .set_min_severity(DEBUG::SEVERITY)
+///     .add_target(|context| println!("reloaded: {}", context.message()))
+/// )]);
+/// log!(DEBUG, "This is logged by the new logger.");
+/// # }
+/// ```
+pub fn reload<I, M, L>(loggers : I)
+    where I : IntoIterator<Item = (M, L)>, M : Into<String>, L : Into<internal::LoggerLocation>
+{
+    internal::reload(
+        loggers.into_iter().map(|(module, location)| (module.into(), internal::RegisteredLogger::from(location.into()))).collect(),
+        Vec::new()
+    );
+}
+
+
+
+/// Flush the targets of every registered
+/// logger.
+/// 
+/// Loggers are flushed in order of their
+/// module paths, and their targets in the
+/// order that they were added. Replaced
+/// loggers that are still in use are
+/// flushed after them.
+pub fn flush() {
+    for logger in internal::registered_loggers().into_iter().chain(internal::retired_loggers()) {
+        logger.flush();
+    }
+}
+
+/// Flush and close the targets of every
+/// registered logger, such as before the
+/// program exits.
+/// 
+/// Loggers are closed in order of their
+/// module paths, and their targets in the
+/// order that they were added. Background
+/// threads used by targets are stopped, so
+/// messages logged afterwards may be lost.
+/// Replaced loggers that are still in use
+/// are closed after them.
+/// 
+/// See `Target::close`.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use loggerithm::{logger, log};
+/// # use loggerithm::level::INFO;
+/// # logger!(super);
+/// fn main() {
+///     log!(INFO, "Starting.");
+///     loggerithm::shutdown();
+/// }
+/// ```
+pub fn shutdown() {
+    for logger in internal::registered_loggers().into_iter().chain(internal::retired_loggers()) {
+        logger.close();
+    }
+}
+
+/// Create a guard that calls `shutdown`
+/// when it is dropped.
+/// 
+/// Keep it alive until the end of `main`,
+/// so that the loggers are shut down when
+/// `main` returns, or unwinds from a panic.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use loggerithm::{logger, log};
+/// # use loggerithm::level::INFO;
+/// # logger!(super);
+/// fn main() {
+///     let _guard = loggerithm::shutdown_guard();
+///     log!(INFO, "This is flushed when `main` returns.");
+/// }
+/// ```
+pub fn shutdown_guard() -> logger::ShutdownGuard {
+    return logger::ShutdownGuard::new();
+}
+
+
+
+/// Set what a `log!` call does when the
+/// module it is in has no registered
+/// logger.
+/// 
+/// By default, the message is logged by
+/// the logger of the closest registered
+/// parent module, or the root logger.
+/// 
+/// # Arguments
+/// 
+/// * `policy` - The new policy.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use loggerithm::logger::UnregisteredPolicy;
+/// loggerithm::set_unregistered_policy(UnregisteredPolicy::Panic);
+/// ```
+pub fn set_unregistered_policy(policy : logger::UnregisteredPolicy) {
+    internal::set_unregistered_policy(policy);
+}
+
+
+
 /// Register a logger for the module
 /// and any submodules that don't
 /// override it.
@@ -128,12 +479,18 @@ impl LogContext<'_> {
 /// # Examples
 /// 
 /// ```
+/// # use loggerithm::logger;
 /// logger!(super);
+/// # fn main() {}
 /// ```
 /// ```
+/// # use loggerithm::logger;
+/// # use loggerithm::logger::Logger;
+/// # use loggerithm::level::NOTICE;
 /// logger!(Logger::new()
 ///     .set_min_severity(NOTICE::SEVERITY)
 /// );
+/// # fn main() {}
 /// ```
 #[macro_export]
 macro_rules! logger {
@@ -158,32 +515,162 @@ macro_rules! logger {
 ///             The severity of the level must be equal
 ///             to or higher than the minimum severity
 ///             level of the active logger.
+/// * `fields` - Optional. Structured fields, as
+///              `key = value` separated by commas,
+///              then a `;`. Values are converted
+///              with `field::Value::from`. Use
+///              `key = %value` to use `Display`,
+///              or `key = ?value` to use `Debug`.
 /// * See `format!` for the remaining arguemnts.
+///   The message and fields are only formatted
+///   if the level is high enough to be logged.
+/// 
+/// If the level has a static severity below
+/// `level::STATIC_MIN_SEVERITY`, this compiles
+/// to nothing.
 /// 
 /// # Examples
 /// ```
+/// # use loggerithm::{logger, log};
+/// # use loggerithm::level::{SUCCESS, FAILURE};
+/// # logger!(super);
+/// # fn main() {
 /// let x = 2 + 3;
 /// if (x == 5) {
 ///     log!(SUCCESS, "The value of x was {}", x);
 /// } else {
 ///     log!(FAILURE, "The value of x was {}, not {}", x, 5)
 /// }
+/// # }
 /// ```
 /// ```
+/// # use loggerithm::{logger, log};
+/// # use loggerithm::level::FATAL;
+/// # logger!(super);
+/// # fn main() {
 /// let hello = 7;
 /// log!(FATAL, "The value of hello is {my_var}", my_var=hello);
+/// # }
+/// ```
+/// ```
+/// # use loggerithm::{logger, log};
+/// # use loggerithm::level::INFO;
+/// # logger!(super);
+/// # fn main() {
+/// let path = "/index.html";
+/// log!(INFO, user_id = 42, path = %path; "Served {} bytes", 1024);
+/// # }
+/// ```
+/// ```
+/// # use loggerithm::{logger, log};
+/// # use loggerithm::level::TRACE;
+/// # logger!(super);
+/// struct Expensive;
+/// impl std::fmt::Display for Expensive {
+///     fn fmt(&self, _ : &mut std::fmt::Formatter) -> std::fmt::Result {
+///         panic!("Not formatted, as the default logger ignores `TRACE`.");
+///     }
+/// }
+/// # fn main() {
+/// log!(TRACE, value = %Expensive; "{}", Expensive);
+/// # }
 /// ```
 #[macro_export]
 macro_rules! log {
+    (@fields [$($level:tt)*] [$($fields:tt)*] $key:ident = % $value:expr , $($rest:tt)*) => {
+        $crate::log!(@fields [$($level)*] [$($fields)* ($key, $crate::field::Value::display(&$value))] $($rest)*)
+    };
+    (@fields [$($level:tt)*] [$($fields:tt)*] $key:ident = % $value:expr ; $($fmt:tt)*) => {
+        $crate::log!(@emit [$($level)*] [$($fields)* ($key, $crate::field::Value::display(&$value))] $($fmt)*)
+    };
+    (@fields [$($level:tt)*] [$($fields:tt)*] $key:ident = ? $value:expr , $($rest:tt)*) => {
+        $crate::log!(@fields [$($level)*] [$($fields)* ($key, $crate::field::Value::debug(&$value))] $($rest)*)
+    };
+    (@fields [$($level:tt)*] [$($fields:tt)*] $key:ident = ? $value:expr ; $($fmt:tt)*) => {
+        $crate::log!(@emit [$($level)*] [$($fields)* ($key, $crate::field::Value::debug(&$value))] $($fmt)*)
+    };
+    (@fields [$($level:tt)*] [$($fields:tt)*] $key:ident = $value:expr , $($rest:tt)*) => {
+        $crate::log!(@fields [$($level)*] [$($fields)* ($key, $crate::field::Value::from($value))] $($rest)*)
+    };
+    (@fields [$($level:tt)*] [$($fields:tt)*] $key:ident = $value:expr ; $($fmt:tt)*) => {
+        $crate::log!(@emit [$($level)*] [$($fields)* ($key, $crate::field::Value::from($value))] $($fmt)*)
+    };
+    (@emit [$($level:tt)*] [$(($key:ident, $value:expr))*] $($fmt:tt)*) => {{
+        __loggerithm_LOGGER::void();
+        if const {$crate::level::statically_enabled($($level)*::STATIC_SEVERITY)} {
+            static CALLSITE : $crate::internal::Callsite = $crate::internal::Callsite::new();
+            let level = &$($level)*::LEVEL;
+            if let Some(logger) = CALLSITE.logger(module_path!(), level.get_severity()) {
+                logger.log_args_with(
+                    level,
+                    module_path!(), (line!(), column!()),
+                    format_args!($($fmt)*),
+                    |_context| {$(
+                        _context.add_field(stringify!($key), $value);
+                    )*}
+                );
+            }
+        }
+    }};
+    ($($level:ident)::*, $key:ident = $($rest:tt)*) => {
+        $crate::log!(@fields [$($level)::*] [] $key = $($rest)*)
+    };
+    ($($level:ident)::*, $($fmt:tt)*) => {
+        $crate::log!(@emit [$($level)::*] [] $($fmt)*)
+    };
+}
+
+
+/// Enter a span, which logs a message
+/// now, and another with the elapsed
+/// time when the returned `span::Span`
+/// is dropped.
+/// 
+/// While the span is entered, every
+/// message logged on the thread has
+/// its name in `LogContext::span`.
+/// 
+/// # Arguments
+/// 
+/// * `level` - The logging level to log the
+///             entry and exit messages at.
+/// * See `format!` for the remaining arguments,
+///   which make the name of the span.
+/// 
+/// If the level has a static severity below
+/// `level::STATIC_MIN_SEVERITY`, the span is
+/// still entered, but nothing is logged.
+/// 
+/// # Examples
+/// ```
+/// # use loggerithm::{logger, log, span};
+/// # use loggerithm::level::INFO;
+/// # logger!(super);
+/// # fn main() {
+/// let _span = span!(INFO, "load_config");
+/// log!(INFO, "This message is in the span `load_config`.");
+/// # }
+/// ```
+/// ```
+/// # use loggerithm::{logger, span};
+/// # use loggerithm::level::DEBUG;
+/// # logger!(super);
+/// # fn main() {
+/// for id in 0..3 {
+///     let _span = span!(DEBUG, "request_{}", id);
+/// }
+/// # }
+/// ```
+#[macro_export]
+macro_rules! span {
     ($($level:ident)::*, $($fmt:tt)*) => {{
         __loggerithm_LOGGER::void();
-        let module = module_path!().to_string();
-        let id_opt = $crate::internal::run_module_logger(module, true, |logger| {
-            logger.log(logger.create_context(
-                &$($level)::*::LEVEL,
-                module_path!().to_string(), (line!(), column!()),
-                format!($($fmt)*)
-            ));
-        });
+        static CALLSITE : $crate::internal::Callsite = $crate::internal::Callsite::new();
+        $crate::span::Span::enter(
+            if const {$crate::level::statically_enabled($($level)::*::STATIC_SEVERITY)} {Some(&CALLSITE)} else {None},
+            &$($level)::*::LEVEL,
+            module_path!(), (line!(), column!()),
+            format!($($fmt)*)
+        )
     }};
 }
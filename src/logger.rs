@@ -1,10 +1,52 @@
 //! Everything related to loggers.
 
 
-use colored::Colorize;
+use std::fmt;
 
 use crate::LogContext;
+use crate::filter::Filter;
+use crate::format::Pattern;
+use crate::internal;
 use crate::level;
+use crate::scope;
+use crate::span;
+use crate::target::{Target, TargetError, Stdout};
+
+
+/// The layout of the messages that
+/// `Logger::default` prints.
+const DEFAULT_PATTERN : &str = " [ {time|bright green,dimmed} ] [ {module:p|green,dimmed} ] [ {level:fp} ] {message:f}";
+
+
+/// What a `log!` call does when the module
+/// it is in has no registered logger, such
+/// as when the `logger!` in it has not been
+/// initialised yet.
+/// 
+/// Set with `loggerithm::set_unregistered_policy`.
+/// 
+/// # Examples
+/// 
+/// ```
+/// use loggerithm::internal::find_module_logger;
+/// use loggerithm::logger::UnregisteredPolicy;
+/// assert!(find_module_logger("not_registered", true).is_some());
+/// loggerithm::set_unregistered_policy(UnregisteredPolicy::Drop);
+/// assert!(find_module_logger("not_registered", true).is_none());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UnregisteredPolicy {
+    /// Log the message with the logger of
+    /// the closest parent module that has
+    /// one registered, which is the root
+    /// logger if none of them do.
+    #[default]
+    Root,
+    /// Ignore the message.
+    Drop,
+    /// Panic.
+    Panic
+}
 
 
 /// An object that determines how logs
@@ -14,37 +56,81 @@ use crate::level;
 /// for logging text. For information on how
 /// to create custom logger, see `examples/
 /// custom_logger.rs`.
+/// 
+/// Loggers are shared between threads,
+/// and can be used, registered and
+/// replaced from any of them. When a
+/// logger is dropped, its targets are
+/// closed.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use loggerithm::{logger, log, log_level, span};
+/// # use loggerithm::logger::Logger;
+/// # use loggerithm::level::LogLevel;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::thread;
+/// static COUNT : AtomicUsize = AtomicUsize::new(0);
+/// fn counter() -> Logger {
+///     return Logger::new()
+///         .add_target(|_| {COUNT.fetch_add(1, Ordering::Relaxed);});
+/// }
+/// logger!(counter());
+/// log_level!(THREADED, LogLevel::new(25));
+/// 
+/// # fn main() {
+/// # // Miri does not run the constructors used by `logger!`.
+/// # loggerithm::reload([(module_path!(), counter())]);
+/// fn assert_send_sync<T : Send + Sync>() {}
+/// assert_send_sync::<Logger>();
+/// assert_send_sync::<LogLevel>();
+/// 
+/// let threads = (0..4).map(|index| thread::spawn(move || {
+///     let _span = span!(THREADED, "thread_{}", index);
+///     for _ in 0..10 {
+///         log!(THREADED, "Hello from thread {}.", index);
+///     }
+///     if (index == 0) {
+///         loggerithm::reload([(module_path!(), counter())]);
+///     }
+/// })).collect::<Vec<_>>();
+/// for thread in threads {
+///     thread.join().unwrap();
+/// }
+/// assert_eq!(COUNT.load(Ordering::Relaxed), 4 * 12);
+/// # }
+/// ```
 pub struct Logger {
-    min_severity : u32,
-    targets      : Vec<Box<dyn Fn(&LogContext)>>
+    min_severity        : u32,
+    targets             : Vec<Box<dyn Target>>,
+    /// The lowest minimum severity of
+    /// the targets.
+    target_min_severity : u32,
+    filter              : Option<Filter>,
+    error_handler       : Box<dyn Fn(&TargetError) + Send + Sync>
 }
 /// Initialisation.
 impl Logger {
     /// Create a new logger object.
     pub fn new() -> Logger {
         return Logger {
-            min_severity : 0,
-            targets      : vec![]
+            min_severity        : 0,
+            targets             : vec![],
+            target_min_severity : u32::MAX,
+            filter              : None,
+            error_handler       : Box::new(print_error)
         };
     }
     /// Create a logger object with the
     /// default severity index and the
     /// log target.
-    pub fn default<'l>() -> Logger {
+    pub fn default() -> Logger {
         return Logger::new()
             .set_min_severity(level::INFO::SEVERITY)
-            .add_target(|context| {
-                println!(
-                    " [ {:0>9} ] [ {} ] [ {} ] {}",
-                    context.time_local()
-                        .format("%Y-%m-%d %H:%M:%S.%f").to_string()
-                        .bright_green().dimmed(),
-                    context.module_p()
-                        .green().dimmed(),
-                    context.level_name_fp(),
-                    context.formatted(context.message())
-                )
-            })
+            .with_target(Stdout::new()
+                .set_formatter(Pattern::new(DEFAULT_PATTERN).unwrap())
+            );
     }
     /// Sets the minimum severity index
     /// required for a message to be logged.
@@ -56,12 +142,98 @@ impl Logger {
     }
     /// Adds a function callback that
     /// will be run when a message is logged.
-    pub fn add_target<F : 'static>(mut self, target : F) -> Logger
-        where F : Fn(&LogContext)
+    /// 
+    /// To add any other `Target`, such as
+    /// `target::File`, use `with_target`.
+    pub fn add_target<F : 'static>(self, target : F) -> Logger
+        where F : Fn(&LogContext) + Send + Sync
     {
+        return self.with_target(target);
+    }
+    /// Adds a target that will be
+    /// written to when a message is logged.
+    /// 
+    /// This can be any `Target`, such as
+    /// `target::File`.
+    /// 
+    /// To give the target its own minimum
+    /// severity or filter, wrap it in a
+    /// `target::Filtered`.
+    pub fn with_target<T : 'static>(mut self, target : T) -> Logger
+        where T : Target
+    {
+        self.target_min_severity = self.target_min_severity.min(target.min_severity());
         self.targets.push(Box::new(target));
         return self;
     }
+    /// Sets the filter that decides if a
+    /// message is given to the targets,
+    /// replacing any previous filter.
+    /// 
+    /// This is checked after the minimum
+    /// severity. To filter the messages given
+    /// to a single target, use a
+    /// `target::Filtered`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `filter` - A `filter::Filter`, or a function
+    ///              that takes a `&LogContext`, and
+    ///              returns `true` to log the message.
+    /// 
+    /// # Generics
+    /// 
+    /// * `F` - The filter.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// # use loggerithm::logger;
+    /// # use loggerithm::logger::Logger;
+    /// use loggerithm::filter::Filter;
+    /// logger!(Logger::default()
+    ///     .set_filter(Filter::module_prefix("noisy_dependency").not())
+    /// );
+    /// # fn main() {}
+    /// ```
+    pub fn set_filter<F>(mut self, filter : F) -> Logger
+        where F : Into<Filter>
+    {
+        self.filter = Some(filter.into());
+        return self;
+    }
+    /// Sets the function that is called when
+    /// a target returns an error.
+    /// 
+    /// By default, errors are printed to the
+    /// standard error.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `error_handler` - The function that handles
+    ///                     the error.
+    /// 
+    /// # Generics
+    /// 
+    /// * `F` - The function that handles the error.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// # use loggerithm::logger;
+    /// # use loggerithm::logger::Logger;
+    /// # use loggerithm::target::TargetError;
+    /// logger!(Logger::new()
+    ///     .set_error_handler(|_ : &TargetError| {})
+    /// );
+    /// # fn main() {}
+    /// ```
+    pub fn set_error_handler<F : 'static>(mut self, error_handler : F) -> Logger
+        where F : Fn(&TargetError) + Send + Sync
+    {
+        self.error_handler = Box::new(error_handler);
+        return self;
+    }
 }
 impl Logger {
     /// Create a LogContext object with
@@ -80,28 +252,225 @@ impl Logger {
     /// 
     /// # Returns
     /// 
-    /// The generated LogContext.
+    /// The generated LogContext, containing
+    /// the spans entered and fields in scope
+    /// on this thread.
     pub fn create_context<'l>(&'l self, level : &'l level::LogLevel, module : String, position : (u32, u32), text : String) -> LogContext<'l> {
-        return LogContext {
-            logger   : self,
+        let mut context = LogContext {
             time     : chrono::Utc::now(),
             module   : module,
             position : position,
             level    : level,
-            text     : text
+            text     : text,
+            spans    : Vec::new(),
+            fields   : Vec::new()
         };
+        span::with_spans(|spans| context.spans = spans.to_vec());
+        scope::with_fields(|fields| {
+            for (key, value) in fields {
+                context.add_field(key.clone(), value.clone());
+            }
+        });
+        return context;
+    }
+    /// Get the minimum severity index
+    /// required for a message to be logged.
+    /// 
+    /// This does not include overrides set
+    /// with `set_module_min_severity`.
+    pub fn get_min_severity(&self) -> u32 {
+        return self.min_severity;
+    }
+    /// Get the minimum severity index
+    /// required for a message logged in a
+    /// module to be logged.
+    /// 
+    /// This is the override set with
+    /// `set_module_min_severity` for the
+    /// closest module, if there is one.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `module` - The module that the message
+    ///              would be logged in.
+    pub fn min_severity_for(&self, module : &str) -> u32 {
+        return internal::find_severity_override(module).unwrap_or(self.min_severity);
+    }
+    /// Get the minimum severity index
+    /// required for a message logged in a
+    /// module to be given to any targets.
+    /// 
+    /// This is never lower than the lowest
+    /// `Target::min_severity` of the targets,
+    /// and is `u32::MAX` if there are none.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `module` - The module that the message
+    ///              would be logged in.
+    pub fn effective_min_severity(&self, module : &str) -> u32 {
+        return self.min_severity_for(module).max(self.target_min_severity);
+    }
+    /// Check if a message logged at a level
+    /// would be given to any targets.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `level`  - The level that the message would
+    ///              be logged at.
+    /// * `module` - The module that the message
+    ///              would be logged in.
+    pub fn enabled(&self, level : &level::LogLevel, module : &str) -> bool {
+        return level.get_severity() >= self.effective_min_severity(module);
+    }
+    /// Invoke all of the log targets, only
+    /// formatting the message if it will be
+    /// logged.
+    /// 
+    /// This is used by the `log!` macro.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `level`    - The level that the message is
+    ///                being logged at.
+    /// * `module`   - The module that invoked the
+    ///                `log!` macro.
+    /// * `position` - A `u32` tuple containing the
+    ///                line number, then the column
+    ///                number.
+    /// * `args`     - The unformatted message, from
+    ///                `format_args!`.
+    pub fn log_args(&self, level : &level::LogLevel, module : &str, position : (u32, u32), args : fmt::Arguments) {
+        self.log_args_with(level, module, position, args, |_| {});
+    }
+    /// Invoke all of the log targets, only
+    /// formatting the message and adding
+    /// structured fields if it will be logged.
+    /// 
+    /// Parts of the filters of the logger, and
+    /// of its `Filtered` targets, that only
+    /// check the module and severity are
+    /// checked before formatting.
+    /// 
+    /// This is used by the `log!` macro.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `fields` - A function that adds fields to
+    ///              the `LogContext`.
+    /// * See `log_args` for the remaining arguments.
+    /// 
+    /// # Generics
+    /// 
+    /// * `F` - The function that adds fields.
+    pub fn log_args_with<F>(&self, level : &level::LogLevel, module : &str, position : (u32, u32), args : fmt::Arguments, fields : F)
+        where F : FnOnce(&mut LogContext)
+    {
+        // The override is only looked up once,
+        // as it needs a lock.
+        let severity     = level.get_severity();
+        let min_severity = self.min_severity_for(module);
+        if (severity >= min_severity.max(self.target_min_severity)
+            && self.filter.as_ref().is_none_or(|filter| filter.matches_metadata(severity, module) != Some(false))
+            && self.targets.iter().any(|target| severity >= target.min_severity() && target.enabled_for(severity, module) != Some(false))
+        ) {
+            let mut context = self.create_context(level, String::from(module), position, fmt::format(args));
+            fields(&mut context);
+            self.log_at(context, min_severity);
+        }
     }
-    /// Invoke all of the log targets.
+    /// Invoke all of the log targets that
+    /// are enabled for the message, and
+    /// whose minimum severity it meets.
+    /// 
+    /// Nothing is invoked if the message does
+    /// not pass the filter of the logger.
+    /// 
+    /// Messages at `FATAL` severity or above
+    /// are flushed straight away, in case the
+    /// program is about to stop.
     /// 
     /// # Arguments
     /// 
     /// * `context` : A `LogContext` containing information
     ///               about the log.
     pub fn log(&self, context : LogContext) {
-        if (context.level().get_severity() >= self.min_severity) {
+        let min_severity = self.min_severity_for(&context.module);
+        self.log_at(context, min_severity);
+    }
+    /// Flush all of the log targets, in the
+    /// order that they were added.
+    pub fn flush(&self) {
+        for target in &self.targets {
+            self.check(target.flush());
+        }
+    }
+    /// Close all of the log targets, in the
+    /// order that they were added.
+    /// 
+    /// See `Target::close`.
+    pub fn close(&self) {
+        for target in &self.targets {
+            self.check(target.close());
+        }
+    }
+    /// Invoke the log targets, once the
+    /// minimum severity of the module has
+    /// been found. See `log`.
+    fn log_at(&self, context : LogContext, min_severity : u32) {
+        let severity = context.level().get_severity();
+        if (severity >= min_severity
+            && self.filter.as_ref().is_none_or(|filter| filter.matches(&context))
+        ) {
             for target in &self.targets {
-                target(&context);
+                if (severity >= target.min_severity() && target.enabled(&context)) {
+                    self.check(target.write(&context));
+                }
+            }
+            if (severity >= level::FATAL::SEVERITY()) {
+                self.flush();
             }
         }
     }
+    /// Give the error from a result to the
+    /// error handler, if there is one.
+    fn check(&self, result : Result<(), TargetError>) {
+        if let Err(error) = result {
+            (self.error_handler)(&error);
+        }
+    }
+}
+impl Drop for Logger {
+    fn drop(&mut self) {
+        self.close();
+    }
+}
+
+/// The default error handler, which
+/// prints the error to the standard error.
+fn print_error(error : &TargetError) {
+    eprintln!("Failed to write to a log target: {}", error);
+}
+
+
+
+/// Shuts down every logger when dropped.
+/// 
+/// Created with `loggerithm::shutdown_guard`.
+#[must_use = "the loggers are shut down when the guard is dropped"]
+pub struct ShutdownGuard {
+    _private : ()
+}
+impl ShutdownGuard {
+    /// Create a new guard.
+    pub(crate) fn new() -> ShutdownGuard {
+        return ShutdownGuard {
+            _private : ()
+        };
+    }
+}
+impl Drop for ShutdownGuard {
+    fn drop(&mut self) {
+        crate::shutdown();
+    }
 }
@@ -0,0 +1,127 @@
+//! Everything related to scoped fields,
+//! which are added to every log message
+//! on a thread while they are in scope.
+//! 
+//! # Examples
+//! 
+//! ```
+//! # use loggerithm::{logger, log};
+//! # use loggerithm::level::INFO;
+//! # logger!(super);
+//! # fn main() {
+//! let _request = loggerithm::scope(("request_id", 1234));
+//! log!(INFO, "This message has a `request_id` field.");
+//! {
+//!     let _user = loggerithm::scope([("user_id", 42), ("request_id", 5678)]);
+//!     log!(INFO, "This message has a `user_id` field, and a different `request_id`.");
+//! }
+//! log!(INFO, "This message has the first `request_id` again.");
+//! # }
+//! ```
+
+
+
+use std::cell::RefCell;
+use std::marker::PhantomData;
+
+use crate::field::Value;
+
+
+
+thread_local! {
+    /// The fields in scope on this thread,
+    /// from outermost to innermost.
+    static FIELDS : RefCell<Vec<(String, Value)>> = const {RefCell::new(Vec::new())};
+}
+
+
+
+/// Something that can be turned into a
+/// list of fields.
+/// 
+/// Implemented for a single `(key, value)`
+/// tuple, and arrays and `Vec`s of them.
+pub trait Fields {
+    /// Get the fields as a list.
+    fn into_fields(self) -> Vec<(String, Value)>;
+}
+impl<K, V> Fields for (K, V)
+    where K : Into<String>, V : Into<Value>
+{
+    fn into_fields(self) -> Vec<(String, Value)> {
+        return vec![(self.0.into(), self.1.into())];
+    }
+}
+impl<K, V, const N : usize> Fields for [(K, V); N]
+    where K : Into<String>, V : Into<Value>
+{
+    fn into_fields(self) -> Vec<(String, Value)> {
+        return self.into_iter().map(|(key, value)| (key.into(), value.into())).collect();
+    }
+}
+impl<K, V> Fields for Vec<(K, V)>
+    where K : Into<String>, V : Into<Value>
+{
+    fn into_fields(self) -> Vec<(String, Value)> {
+        return self.into_iter().map(|(key, value)| (key.into(), value.into())).collect();
+    }
+}
+
+
+
+/// Add fields to every log message created
+/// on this thread, until the returned guard
+/// is dropped.
+/// 
+/// Scopes can be nested. Inner scopes shadow
+/// fields with the same key in outer scopes,
+/// and fields passed into the `log!` macro
+/// shadow all scoped fields.
+/// 
+/// # Arguments
+/// 
+/// * `fields` - The fields to add.
+/// 
+/// # Returns
+/// 
+/// A guard that removes the fields when
+/// dropped.
+#[must_use = "The fields are removed as soon as the guard is dropped."]
+pub fn scope<F>(fields : F) -> ScopeGuard
+    where F : Fields
+{
+    return FIELDS.with(|scoped| {
+        let mut scoped = scoped.borrow_mut();
+        let depth = scoped.len();
+        scoped.extend(fields.into_fields());
+        return ScopeGuard {
+            depth,
+            thread : PhantomData
+        };
+    });
+}
+
+/// Call a function with every field in
+/// scope on this thread, from outermost
+/// to innermost.
+pub(crate) fn with_fields<F>(callback : F)
+    where F : FnOnce(&[(String, Value)])
+{
+    FIELDS.with(|scoped| callback(&scoped.borrow()));
+}
+
+
+
+/// Removes the fields added by `scope`
+/// when dropped.
+pub struct ScopeGuard {
+    depth  : usize,
+    /// The guard must be dropped on the
+    /// thread that created it.
+    thread : PhantomData<*const ()>
+}
+impl Drop for ScopeGuard {
+    fn drop(&mut self) {
+        FIELDS.with(|scoped| scoped.borrow_mut().truncate(self.depth));
+    }
+}
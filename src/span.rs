@@ -0,0 +1,134 @@
+//! Everything related to spans, which
+//! time a section of code and name it in
+//! every log message logged inside it.
+//! 
+//! # Examples
+//! 
+//! ```
+//! # use loggerithm::{logger, log, span};
+//! # use loggerithm::level::{DEBUG, INFO};
+//! # logger!(super);
+//! # fn main() {
+//! let _load = span!(INFO, "load_config");
+//! {
+//!     let _parse = span!(DEBUG, "parse");
+//!     log!(INFO, "This message is in the span `load_config::parse`.");
+//! }
+//! log!(INFO, "This message is in the span `load_config`.");
+//! # }
+//! ```
+
+
+
+use std::cell::RefCell;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+
+use crate::internal::{self, Callsite};
+use crate::level::LogLevel;
+
+
+
+thread_local! {
+    /// The names of the spans entered on
+    /// this thread, from outermost to
+    /// innermost.
+    static SPANS : RefCell<Vec<String>> = const {RefCell::new(Vec::new())};
+}
+
+/// Call a function with the names of
+/// every span entered on this thread,
+/// from outermost to innermost.
+pub(crate) fn with_spans<F>(callback : F)
+    where F : FnOnce(&[String])
+{
+    SPANS.with(|spans| callback(&spans.borrow()));
+}
+
+
+
+/// A section of code created by `span!`.
+/// 
+/// Logs a message when entered, and
+/// another when dropped, with the elapsed
+/// time in the `elapsed_us` field.
+#[must_use = "The span is exited as soon as it is dropped."]
+pub struct Span {
+    callsite : Option<&'static Callsite>,
+    level    : &'static LogLevel,
+    module   : &'static str,
+    position : (u32, u32),
+    depth    : usize,
+    start    : Instant,
+    /// The span must be dropped on the
+    /// thread that entered it.
+    thread   : PhantomData<*const ()>
+}
+/// Initialisation.
+impl Span {
+    /// Enter a span. Use the `span!` macro
+    /// instead.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `callsite` - The cache of the `span!` callsite,
+    ///                or `None` if the level is
+    ///                statically disabled.
+    /// * `level`    - The logging level to log at.
+    /// * `module`   - The module that the span is in.
+    /// * `position` - The line and column of the span.
+    /// * `name`     - The name of the span.
+    #[doc(hidden)]
+    pub fn enter(callsite : Option<&'static Callsite>, level : &'static LogLevel, module : &'static str, position : (u32, u32), name : String) -> Span {
+        let (depth, len) = SPANS.with(|spans| {
+            let mut spans = spans.borrow_mut();
+            spans.push(name);
+            (spans.len() - 1, spans.iter().map(|name| name.len() + 2).sum::<usize>() - 2)
+        });
+        internal::MAX_SPAN_LEN.fetch_max(len, Ordering::Relaxed);
+        let span = Span {
+            callsite,
+            level,
+            module,
+            position,
+            depth,
+            start  : Instant::now(),
+            thread : PhantomData
+        };
+        span.log(format_args!("Entered."), None);
+        return span;
+    }
+}
+/// Data access functions.
+impl Span {
+    /// Get the time since the span
+    /// was entered.
+    pub fn elapsed(&self) -> Duration {
+        return self.start.elapsed();
+    }
+}
+impl Span {
+    /// Log a message at the level and
+    /// position of the span, with the
+    /// elapsed time as a field if given.
+    fn log(&self, args : fmt::Arguments, elapsed : Option<Duration>) {
+        if let Some(callsite) = self.callsite {
+            if let Some(logger) = callsite.logger(self.module, self.level.get_severity()) {
+                logger.log_args_with(self.level, self.module, self.position, args, |context| {
+                    if let Some(elapsed) = elapsed {
+                        context.add_field("elapsed_us", u64::try_from(elapsed.as_micros()).unwrap_or(u64::MAX));
+                    }
+                });
+            }
+        }
+    }
+}
+impl Drop for Span {
+    fn drop(&mut self) {
+        let elapsed = self.elapsed();
+        self.log(format_args!("Exited after {:?}.", elapsed), Some(elapsed));
+        SPANS.with(|spans| spans.borrow_mut().truncate(self.depth));
+    }
+}
@@ -0,0 +1,227 @@
+//! Everything related to log targets.
+//! 
+//! # Examples
+//! 
+//! ```no_run
+//! # use loggerithm::{logger, log};
+//! # use loggerithm::logger::Logger;
+//! # use loggerithm::level::INFO;
+//! use loggerithm::target;
+//! logger!(Logger::new()
+//!     .with_target(target::File::open("logs/app.log").unwrap())
+//! );
+//! # fn main() {
+//! log!(INFO, "This is written to `logs/app.log`.");
+//! # }
+//! ```
+
+
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+use crate::LogContext;
+
+pub mod stream;
+pub use stream::{Stdout, Stderr};
+pub mod file;
+pub use file::{File, FileOptions, FlushPolicy, SyncPolicy};
+pub mod rotating;
+pub use rotating::{RotatingFile, RotatingFileOptions, Rotation, Naming};
+pub mod compressed;
+pub use compressed::{Compressed, CompressedOptions, Compression};
+pub mod asynchronous;
+pub use asynchronous::{Async, AsyncOptions, Overflow};
+pub mod filtered;
+pub use filtered::Filtered;
+
+
+
+/// Something that log messages can
+/// be written to.
+/// 
+/// This is implemented for any function
+/// callback that takes a `&LogContext`.
+/// 
+/// Targets are shared between threads,
+/// so they must be `Send` and `Sync`.
+/// 
+/// Errors returned by a target are given
+/// to the error handler of the logger that
+/// it was added to. See
+/// `Logger::set_error_handler`.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use loggerithm::{logger, log, LogContext};
+/// # use loggerithm::logger::Logger;
+/// # use loggerithm::level::INFO;
+/// use std::sync::Mutex;
+/// use loggerithm::target::{Target, TargetError};
+/// struct Collect {
+///     lines : Mutex<Vec<String>>
+/// }
+/// impl Target for Collect {
+///     fn write(&self, context : &LogContext) -> Result<(), TargetError> {
+///         let mut lines = self.lines.lock()
+///             .map_err(|_| TargetError::Other(String::from("Poisoned.")))?;
+///         lines.push(context.message());
+///         return Ok(());
+///     }
+///     fn enabled(&self, context : &LogContext) -> bool {
+///         return ! context.message().is_empty();
+///     }
+/// }
+/// logger!(Logger::new()
+///     .with_target(Collect {lines : Mutex::new(Vec::new())})
+/// );
+/// # fn main() {
+/// log!(INFO, "This is collected.");
+/// log!(INFO, "");
+/// # }
+/// ```
+/// 
+/// ```compile_fail
+/// # use loggerithm::logger::Logger;
+/// use std::rc::Rc;
+/// let prefix = Rc::new(String::from("> "));
+/// let logger = Logger::new()
+///     .add_target(move |context| println!("{}{}", prefix, context.message()));
+/// ```
+pub trait Target : Send + Sync {
+    /// Write a log message to the target.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `context` - A `LogContext` containing information
+    ///               about the log.
+    /// 
+    /// # Returns
+    /// 
+    /// The error that occured while writing,
+    /// if there was one.
+    fn write(&self, context : &LogContext) -> Result<(), TargetError>;
+    /// Flush any messages that the target
+    /// has buffered.
+    /// 
+    /// # Returns
+    /// 
+    /// The error that occured while flushing,
+    /// if there was one.
+    fn flush(&self) -> Result<(), TargetError> {
+        return Ok(());
+    }
+    /// Flush the target, and release anything
+    /// that it needs to be finished, such as
+    /// a background thread.
+    /// 
+    /// This is called once logging is done,
+    /// by `loggerithm::shutdown`, or when the
+    /// logger that owns the target is dropped,
+    /// so it may be called more than once.
+    /// Messages written after this may be
+    /// lost, and return `TargetError::Closed`.
+    /// 
+    /// By default, this calls `flush`.
+    fn close(&self) -> Result<(), TargetError> {
+        return self.flush();
+    }
+    /// Check if the target wants a log
+    /// message, before it is written.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `context` - A `LogContext` containing information
+    ///               about the log.
+    /// 
+    /// # Returns
+    /// 
+    /// Whether `write` should be called. By
+    /// default, this is always `true`.
+    fn enabled(&self, context : &LogContext) -> bool {
+        let _ = context;
+        return true;
+    }
+    /// Check if the target wants a log
+    /// message, using only its severity and
+    /// module, before it is formatted.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `severity` - The severity of the message.
+    /// * `module`   - The module that the message
+    ///                is logged in.
+    /// 
+    /// # Returns
+    /// 
+    /// `Some(false)` if `enabled` would return
+    /// `false` for any message with that severity
+    /// and module, or `None` if it depends on the
+    /// rest of the message. By default, this is
+    /// always `None`.
+    fn enabled_for(&self, severity : u32, module : &str) -> Option<bool> {
+        let _ = (severity, module);
+        return None;
+    }
+    /// Get the minimum severity index
+    /// required for a message to be given
+    /// to the target.
+    /// 
+    /// This is read when the target is added
+    /// to a logger, so that messages that no
+    /// target wants are skipped before they
+    /// are formatted. It should not change.
+    /// 
+    /// By default, this is `0`. See `Filtered`.
+    fn min_severity(&self) -> u32 {
+        return 0;
+    }
+}
+impl<F> Target for F
+    where F : Fn(&LogContext) + Send + Sync
+{
+    fn write(&self, context : &LogContext) -> Result<(), TargetError> {
+        self(context);
+        return Ok(());
+    }
+}
+
+
+
+/// A problem that occured while writing
+/// to a target.
+#[derive(Debug)]
+pub enum TargetError {
+    /// An error while writing to a file or
+    /// stream.
+    Io(io::Error),
+    /// The target has been closed, or its
+    /// background thread has stopped.
+    Closed,
+    /// Any other error.
+    Other(String)
+}
+impl fmt::Display for TargetError {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        return match (self) {
+            TargetError::Io(error)      => write!(f, "{}", error),
+            TargetError::Closed         => write!(f, "The target has been closed."),
+            TargetError::Other(message) => write!(f, "{}", message)
+        };
+    }
+}
+impl Error for TargetError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        return match (self) {
+            TargetError::Io(error) => Some(error),
+            _                      => None
+        };
+    }
+}
+impl From<io::Error> for TargetError {
+    fn from(error : io::Error) -> TargetError {
+        return TargetError::Io(error);
+    }
+}
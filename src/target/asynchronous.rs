@@ -0,0 +1,393 @@
+//! A target that writes to another target
+//! on a background thread.
+
+
+
+use std::collections::VecDeque;
+use std::io;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::thread::{self, JoinHandle};
+
+use crate::{LogContext, OwnedLogContext};
+use crate::level;
+use crate::target::{Target, TargetError};
+
+
+
+/// What an `Async` target does with a new
+/// message when its queue is full.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Overflow {
+    /// Wait for space in the queue, stalling
+    /// the thread that is logging.
+    Block,
+    /// Drop the new message.
+    DropNewest,
+    /// Drop the oldest message in the queue,
+    /// then add the new message.
+    DropOldest
+}
+
+
+
+/// Options used to create an `Async` target.
+/// 
+/// # Examples
+/// 
+/// ```no_run
+/// use loggerithm::target::{AsyncOptions, Overflow, File};
+/// let file = AsyncOptions::new()
+///     .set_capacity(4096)
+///     .set_overflow(Overflow::DropOldest)
+///     .wrap(File::open("logs/app.log").unwrap())
+///     .unwrap();
+/// ```
+pub struct AsyncOptions {
+    capacity : usize,
+    overflow : Overflow
+}
+/// Initialisation.
+impl AsyncOptions {
+    /// Create the default options.
+    /// 
+    /// By default, up to 1024 messages are
+    /// queued, and new messages are dropped
+    /// when the queue is full.
+    pub fn new() -> AsyncOptions {
+        return AsyncOptions {
+            capacity : 1024,
+            overflow : Overflow::DropNewest
+        };
+    }
+    /// Sets the number of messages that can
+    /// be waiting to be written.
+    pub fn set_capacity(mut self, capacity : usize) -> AsyncOptions {
+        self.capacity = capacity.max(1);
+        return self;
+    }
+    /// Sets what happens to a new message
+    /// when the queue is full.
+    pub fn set_overflow(mut self, overflow : Overflow) -> AsyncOptions {
+        self.overflow = overflow;
+        return self;
+    }
+    /// Start the background thread that
+    /// writes to a target.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `target` - The target to write to.
+    /// 
+    /// # Returns
+    /// 
+    /// The async target, or the error that
+    /// occured while starting the thread.
+    pub fn wrap<T : 'static>(self, target : T) -> io::Result<Async>
+        where T : Target
+    {
+        let shared = Arc::new(AsyncShared {
+            capacity  : self.capacity,
+            overflow  : self.overflow,
+            queue     : Mutex::new(AsyncQueue {
+                messages : VecDeque::new(),
+                logs     : 0,
+                dropped  : 0,
+                error    : None,
+                closed   : false
+            }),
+            not_empty : Condvar::new(),
+            not_full  : Condvar::new(),
+            dropped   : AtomicU64::new(0)
+        });
+        let target  : Arc<dyn Target> = Arc::new(target);
+        let worker  = Arc::clone(&shared);
+        let wrapped = Arc::clone(&target);
+        let handle  = thread::Builder::new()
+            .name(String::from("loggerithm-async"))
+            .spawn(move || worker.run(&*wrapped))?;
+        return Ok(Async {
+            target,
+            shared,
+            handle : Mutex::new(Some(handle))
+        });
+    }
+}
+
+
+
+/// A target that queues each log message,
+/// then writes it to another target on a
+/// background thread.
+/// 
+/// When messages are dropped because the
+/// queue is full, a `WARN` message with the
+/// number dropped is written to the target
+/// in their place. These reports do not
+/// take up space in the queue.
+/// 
+/// Errors returned by the wrapped target
+/// are returned the next time that a
+/// message is written, or the target is
+/// flushed or closed.
+pub struct Async {
+    target : Arc<dyn Target>,
+    shared : Arc<AsyncShared>,
+    handle : Mutex<Option<JoinHandle<()>>>
+}
+/// Initialisation.
+impl Async {
+    /// Wrap a target with the default options.
+    /// 
+    /// See `AsyncOptions::new`.
+    pub fn new<T : 'static>(target : T) -> io::Result<Async>
+        where T : Target
+    {
+        return AsyncOptions::new().wrap(target);
+    }
+    /// Create the default options, used to
+    /// wrap a target with custom options.
+    pub fn options() -> AsyncOptions {
+        return AsyncOptions::new();
+    }
+}
+/// Data access functions.
+impl Async {
+    /// Get the total number of messages that
+    /// have been dropped because the queue
+    /// was full.
+    pub fn dropped(&self) -> u64 {
+        return self.shared.dropped.load(Ordering::Relaxed);
+    }
+}
+impl Target for Async {
+    fn write(&self, context : &LogContext) -> Result<(), TargetError> {
+        self.shared.push(AsyncMessage::Log(context.to_owned_context()))?;
+        return self.shared.take_error();
+    }
+    fn flush(&self) -> Result<(), TargetError> {
+        let (done, wait) = mpsc::channel();
+        self.shared.push(AsyncMessage::Flush(done))?;
+        let _ = wait.recv();
+        return self.shared.take_error();
+    }
+    /// Write the queued messages, then close
+    /// the wrapped target and stop the
+    /// background thread. Messages written
+    /// after this are ignored.
+    fn close(&self) -> Result<(), TargetError> {
+        let mut queue = self.shared.lock();
+        queue.closed = true;
+        queue.report_dropped();
+        drop(queue);
+        self.shared.not_empty.notify_all();
+        self.shared.not_full.notify_all();
+        if let Some(handle) = self.handle.lock().unwrap_or_else(|error| error.into_inner()).take() {
+            if (handle.join().is_err()) {
+                return Err(TargetError::Other(String::from("The async thread panicked.")));
+            }
+        }
+        return self.shared.take_error();
+    }
+    fn enabled(&self, context : &LogContext) -> bool {
+        return self.target.enabled(context);
+    }
+    fn enabled_for(&self, severity : u32, module : &str) -> Option<bool> {
+        return self.target.enabled_for(severity, module);
+    }
+    fn min_severity(&self) -> u32 {
+        return self.target.min_severity();
+    }
+}
+impl Drop for Async {
+    fn drop(&mut self) {
+        let _ = self.close();
+    }
+}
+
+
+
+/// A message sent to the background thread.
+enum AsyncMessage {
+    /// A log message to write.
+    Log(OwnedLogContext),
+    /// Flush the target, then reply.
+    Flush(Sender<()>),
+    /// Report that messages were dropped.
+    Dropped(u64)
+}
+
+/// The queue of messages waiting to be
+/// written.
+struct AsyncQueue {
+    messages : VecDeque<AsyncMessage>,
+    /// The number of log messages in
+    /// `messages`, which is limited by
+    /// the capacity.
+    logs     : usize,
+    /// The number of messages dropped
+    /// since the last report was queued.
+    dropped  : u64,
+    /// The first error returned by the
+    /// target that has not been returned
+    /// to the logger.
+    error    : Option<TargetError>,
+    closed   : bool
+}
+impl AsyncQueue {
+    /// Queue a report of the dropped
+    /// messages, if there are any.
+    /// 
+    /// The report is merged into the last
+    /// queued message, if it is a report.
+    fn report_dropped(&mut self) {
+        if (self.dropped > 0) {
+            match (self.messages.back_mut()) {
+                Some(AsyncMessage::Dropped(dropped)) => *dropped += self.dropped,
+                _ => self.messages.push_back(AsyncMessage::Dropped(self.dropped))
+            }
+            self.dropped = 0;
+        }
+    }
+    /// Replace the oldest queued log message
+    /// with a report that it was dropped.
+    /// 
+    /// The report is merged into the message
+    /// before it, if that is a report.
+    /// 
+    /// # Returns
+    /// 
+    /// `false` if there are no log messages
+    /// in the queue.
+    fn drop_oldest(&mut self) -> bool {
+        let Some(index) = self.messages.iter().position(|message| matches!(message, AsyncMessage::Log(_))) else {
+            return false;
+        };
+        self.logs -= 1;
+        if let Some(AsyncMessage::Dropped(dropped)) = index.checked_sub(1).and_then(|before| self.messages.get_mut(before)) {
+            *dropped += 1;
+            self.messages.remove(index);
+        } else {
+            self.messages[index] = AsyncMessage::Dropped(1);
+        }
+        return true;
+    }
+}
+
+/// The state shared with the background
+/// thread.
+struct AsyncShared {
+    capacity  : usize,
+    overflow  : Overflow,
+    queue     : Mutex<AsyncQueue>,
+    not_empty : Condvar,
+    not_full  : Condvar,
+    dropped   : AtomicU64
+}
+impl AsyncShared {
+    /// Lock the queue.
+    fn lock(&self) -> MutexGuard<'_, AsyncQueue> {
+        return self.queue.lock().unwrap_or_else(|error| error.into_inner());
+    }
+    /// Keep the error from a result, if
+    /// there is not one already.
+    fn check(&self, result : Result<(), TargetError>) {
+        if let Err(error) = result {
+            self.lock().error.get_or_insert(error);
+        }
+    }
+    /// Take the error that has been kept.
+    fn take_error(&self) -> Result<(), TargetError> {
+        return match (self.lock().error.take()) {
+            Some(error) => Err(error),
+            None        => Ok(())
+        };
+    }
+    /// Add a message to the queue, following
+    /// the overflow policy if it is full.
+    /// 
+    /// Only log messages are limited by the
+    /// capacity. Flush messages, and reports
+    /// of dropped messages, are always added.
+    /// 
+    /// # Returns
+    /// 
+    /// `TargetError::Closed` if the target has
+    /// been closed. Dropped messages are not
+    /// an error.
+    fn push(&self, message : AsyncMessage) -> Result<(), TargetError> {
+        let mut queue = self.lock();
+        let log = matches!(message, AsyncMessage::Log(_));
+        if (log) {
+            while (! queue.closed && queue.logs >= self.capacity) {
+                match (self.overflow) {
+                    Overflow::Block => {
+                        queue = self.not_full.wait(queue).unwrap_or_else(|error| error.into_inner());
+                    },
+                    Overflow::DropNewest => {
+                        queue.dropped += 1;
+                        self.dropped.fetch_add(1, Ordering::Relaxed);
+                        return Ok(());
+                    },
+                    Overflow::DropOldest => {
+                        if (! queue.drop_oldest()) {
+                            break;
+                        }
+                        self.dropped.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+            }
+        }
+        if (queue.closed) {
+            return Err(TargetError::Closed);
+        }
+        queue.report_dropped();
+        queue.messages.push_back(message);
+        if (log) {
+            queue.logs += 1;
+        }
+        self.not_empty.notify_one();
+        return Ok(());
+    }
+    /// Write messages to the target until
+    /// the queue is closed and empty, then
+    /// close the target.
+    fn run(&self, target : &dyn Target) {
+        loop {
+            let message = {
+                let mut queue = self.lock();
+                loop {
+                    if let Some(message) = queue.messages.pop_front() {
+                        if (matches!(message, AsyncMessage::Log(_))) {
+                            queue.logs -= 1;
+                        }
+                        break Some(message);
+                    }
+                    if (queue.closed) {
+                        break None;
+                    }
+                    queue = self.not_empty.wait(queue).unwrap_or_else(|error| error.into_inner());
+                }
+            };
+            self.not_full.notify_one();
+            match (message) {
+                Some(AsyncMessage::Log(context)) => self.check(target.write(&context.context())),
+                Some(AsyncMessage::Flush(done))  => {
+                    self.check(target.flush());
+                    let _ = done.send(());
+                },
+                Some(AsyncMessage::Dropped(dropped)) => {
+                    let context = OwnedLogContext::new(&level::WARN::LEVEL, module_path!(),
+                        format!("Dropped {} {} because the queue was full.", dropped, if (dropped == 1) {"message"} else {"messages"})
+                    );
+                    self.check(target.write(&context.context()));
+                },
+                None => {
+                    self.check(target.close());
+                    return;
+                }
+            }
+        }
+    }
+}
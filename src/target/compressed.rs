@@ -0,0 +1,398 @@
+//! A target that writes to a compressed file.
+
+
+
+use std::fs;
+use std::io::{self, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::mpsc::{self, Sender, RecvTimeoutError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use flate2::write::GzEncoder;
+
+use crate::LogContext;
+use crate::format::Formatter;
+use crate::target::{Target, TargetError};
+use crate::target::file;
+
+
+
+/// The format used to compress a
+/// `Compressed` target.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compression {
+    /// A gzip stream.
+    Gzip,
+    /// A zstd stream.
+    /// 
+    /// Requires the `zstd` feature.
+    #[cfg(feature = "zstd")]
+    Zstd
+}
+
+
+
+/// Options used to open a `Compressed` target.
+/// 
+/// # Examples
+/// 
+/// ```no_run
+/// use loggerithm::target::{CompressedOptions, Compression};
+/// let file = CompressedOptions::new()
+///     .set_compression(Compression::Gzip)
+///     .set_level(9)
+///     .open("logs/trace.log.gz")
+///     .unwrap();
+/// ```
+pub struct CompressedOptions {
+    compression    : Compression,
+    level          : Option<u32>,
+    append         : bool,
+    create_dirs    : bool,
+    flush_interval : Duration,
+    formatter      : Box<dyn Formatter>
+}
+/// Initialisation.
+impl CompressedOptions {
+    /// Create the default options.
+    /// 
+    /// By default, the file is gzipped at the
+    /// default level, appended to, missing parent
+    /// directories are created, and the stream is
+    /// finished after a second without messages.
+    pub fn new() -> CompressedOptions {
+        return CompressedOptions {
+            compression    : Compression::Gzip,
+            level          : None,
+            append         : true,
+            create_dirs    : true,
+            flush_interval : Duration::from_secs(1),
+            formatter      : Box::new(file::plain)
+        };
+    }
+    /// Sets the compression format.
+    pub fn set_compression(mut self, compression : Compression) -> CompressedOptions {
+        self.compression = compression;
+        return self;
+    }
+    /// Sets the compression level.
+    /// 
+    /// For gzip, this is between `0` and `9`.
+    /// For zstd, this is between `1` and `22`.
+    pub fn set_level(mut self, level : u32) -> CompressedOptions {
+        self.level = Some(level);
+        return self;
+    }
+    /// Sets whether to append to the file
+    /// instead of truncating it when opened.
+    pub fn set_append(mut self, append : bool) -> CompressedOptions {
+        self.append = append;
+        return self;
+    }
+    /// Sets whether to create any missing
+    /// parent directories when opened.
+    pub fn set_create_dirs(mut self, create_dirs : bool) -> CompressedOptions {
+        self.create_dirs = create_dirs;
+        return self;
+    }
+    /// Sets how long the background thread
+    /// waits without a message before it
+    /// finishes the stream, making the file
+    /// decodable.
+    pub fn set_flush_interval(mut self, flush_interval : Duration) -> CompressedOptions {
+        self.flush_interval = flush_interval;
+        return self;
+    }
+    /// Sets the formatter that turns a log
+    /// message into a line of text.
+    /// 
+    /// See `FileOptions::set_formatter`.
+    pub fn set_formatter<F : 'static>(mut self, formatter : F) -> CompressedOptions
+        where F : Formatter
+    {
+        self.formatter = Box::new(formatter);
+        return self;
+    }
+    /// Open the file, and start the background
+    /// thread that compresses into it.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `path` - The path of the file to write to.
+    /// 
+    /// # Returns
+    /// 
+    /// The file target, or the error that occured
+    /// while opening it.
+    pub fn open<P>(self, path : P) -> io::Result<Compressed>
+        where P : AsRef<Path>
+    {
+        let path = path.as_ref().to_path_buf();
+        let file = file::open_file(&path, self.append, self.create_dirs)?;
+        let mut worker = CompressedWorker {
+            compression : self.compression,
+            level       : self.level,
+            file,
+            start       : 0,
+            encoder     : None,
+            error       : None
+        };
+        let interval = self.flush_interval;
+        let (sender, receiver) = mpsc::channel();
+        let handle = thread::Builder::new()
+            .name(String::from("loggerithm-compressed"))
+            .spawn(move || {
+                loop {
+                    match (receiver.recv_timeout(interval)) {
+                        Ok(CompressedMessage::Line(line)) => {
+                            let result = worker.write(line.as_bytes());
+                            worker.check(result);
+                        },
+                        Ok(CompressedMessage::Flush(done)) => {
+                            let result = worker.finish();
+                            worker.check(result);
+                            let _ = done.send(worker.take_error());
+                        },
+                        Err(RecvTimeoutError::Timeout) => {
+                            let result = worker.finish();
+                            worker.check(result);
+                        },
+                        Err(RecvTimeoutError::Disconnected) => {
+                            let result = worker.finish();
+                            worker.check(result);
+                            return worker.take_error();
+                        }
+                    }
+                }
+            })?;
+        return Ok(Compressed {
+            path,
+            formatter : self.formatter,
+            sender    : Mutex::new(Some(sender)),
+            handle    : Mutex::new(Some(handle))
+        });
+    }
+}
+
+
+
+/// A target that writes each log message
+/// to a compressed file as a line of text.
+/// 
+/// Compression is done on a background
+/// thread. Every time the target is flushed,
+/// goes a while without messages, or is
+/// dropped, the stream is finished so that
+/// the file can be decoded. Writing then
+/// continues in a new stream, which most
+/// decoders read as part of the same file.
+/// 
+/// Errors that occur on the background
+/// thread are returned the next time that
+/// the target is flushed or closed. A
+/// stream that fails is cut from the file,
+/// so the file stays decodable, and writing
+/// continues in a new stream.
+pub struct Compressed {
+    path      : PathBuf,
+    formatter : Box<dyn Formatter>,
+    sender    : Mutex<Option<Sender<CompressedMessage>>>,
+    handle    : Mutex<Option<JoinHandle<io::Result<()>>>>
+}
+/// Initialisation.
+impl Compressed {
+    /// Open a file with the default options.
+    /// 
+    /// See `CompressedOptions::new`.
+    pub fn open<P>(path : P) -> io::Result<Compressed>
+        where P : AsRef<Path>
+    {
+        return CompressedOptions::new().open(path);
+    }
+    /// Create the default options, used to
+    /// open a file with custom options.
+    pub fn options() -> CompressedOptions {
+        return CompressedOptions::new();
+    }
+}
+/// Data access functions.
+impl Compressed {
+    /// Get the path of the file.
+    pub fn path(&self) -> &Path {
+        return &self.path;
+    }
+}
+impl Compressed {
+    /// Send a message to the background thread.
+    fn send(&self, message : CompressedMessage) -> bool {
+        return match (&*self.sender.lock().unwrap_or_else(|error| error.into_inner())) {
+            Some(sender) => sender.send(message).is_ok(),
+            None         => false
+        };
+    }
+}
+impl Target for Compressed {
+    fn write(&self, context : &LogContext) -> Result<(), TargetError> {
+        if (! self.send(CompressedMessage::Line(format!("{}\n", self.formatter.format(context))))) {
+            return Err(TargetError::Closed);
+        }
+        return Ok(());
+    }
+    fn flush(&self) -> Result<(), TargetError> {
+        let (done, wait) = mpsc::channel();
+        if (! self.send(CompressedMessage::Flush(done))) {
+            return Err(TargetError::Closed);
+        }
+        return match (wait.recv()) {
+            Ok(result) => Ok(result?),
+            Err(_)     => Err(TargetError::Closed)
+        };
+    }
+    /// Finish the stream, and stop the
+    /// background thread. Messages written
+    /// after this are ignored.
+    fn close(&self) -> Result<(), TargetError> {
+        self.sender.lock().unwrap_or_else(|error| error.into_inner()).take();
+        return match (self.handle.lock().unwrap_or_else(|error| error.into_inner()).take()) {
+            Some(handle) => match (handle.join()) {
+                Ok(result) => Ok(result?),
+                Err(_)     => Err(TargetError::Other(String::from("The compression thread panicked.")))
+            },
+            None => Ok(())
+        };
+    }
+}
+impl Drop for Compressed {
+    fn drop(&mut self) {
+        let _ = self.close();
+    }
+}
+
+
+
+/// A message sent to the background thread.
+enum CompressedMessage {
+    /// A line to compress.
+    Line(String),
+    /// Finish the stream, then reply with
+    /// any error that has occured.
+    Flush(Sender<io::Result<()>>)
+}
+
+/// The state of the background thread.
+struct CompressedWorker {
+    compression : Compression,
+    level       : Option<u32>,
+    file        : fs::File,
+    /// The length of the file when the
+    /// current stream was started.
+    start       : u64,
+    encoder     : Option<CompressedEncoder>,
+    /// The first error that has occured
+    /// since the last flush.
+    error       : Option<io::Error>
+}
+impl CompressedWorker {
+    /// Keep the error from a result, if
+    /// there is not one already.
+    fn check(&mut self, result : io::Result<()>) {
+        if let Err(error) = result {
+            self.error.get_or_insert(error);
+        }
+    }
+    /// Take the error that has been kept.
+    fn take_error(&mut self) -> io::Result<()> {
+        return match (self.error.take()) {
+            Some(error) => Err(error),
+            None        => Ok(())
+        };
+    }
+    /// Compress some data, starting a new
+    /// stream if needed.
+    /// 
+    /// If this fails, the current stream is
+    /// thrown away, and the next write starts
+    /// a new one.
+    fn write(&mut self, data : &[u8]) -> io::Result<()> {
+        let encoder = match (&mut self.encoder) {
+            Some(encoder) => encoder,
+            None          => {
+                self.start = self.file.metadata()?.len();
+                // The stream writes to a copy of the
+                // handle, so that the file is kept if
+                // the stream fails.
+                self.encoder.insert(CompressedEncoder::new(self.compression, self.level, self.file.try_clone()?)?)
+            }
+        };
+        let result = encoder.write_all(data);
+        if (result.is_err()) {
+            self.discard();
+        }
+        return result;
+    }
+    /// Finish the current stream, if there
+    /// is one, and flush the file.
+    /// 
+    /// If this fails, the stream is thrown
+    /// away.
+    fn finish(&mut self) -> io::Result<()> {
+        if let Some(encoder) = self.encoder.take() {
+            if let Err(error) = encoder.finish() {
+                self.discard();
+                return Err(error);
+            }
+        }
+        return self.file.flush();
+    }
+    /// Throw away the current stream, and
+    /// remove anything that it wrote, so that
+    /// the file can still be decoded.
+    /// 
+    /// The file is also moved back to where
+    /// the stream started, in case it was not
+    /// opened in append mode.
+    fn discard(&mut self) {
+        self.encoder = None;
+        let _ = self.file.set_len(self.start);
+        let _ = self.file.seek(SeekFrom::Start(self.start));
+    }
+}
+
+/// A compression stream.
+enum CompressedEncoder {
+    Gzip(GzEncoder<fs::File>),
+    #[cfg(feature = "zstd")]
+    Zstd(zstd::Encoder<'static, fs::File>)
+}
+impl CompressedEncoder {
+    /// Start a new stream at the end of a file.
+    fn new(compression : Compression, level : Option<u32>, file : fs::File) -> io::Result<CompressedEncoder> {
+        return Ok(match (compression) {
+            Compression::Gzip => CompressedEncoder::Gzip(GzEncoder::new(file,
+                level.map(flate2::Compression::new).unwrap_or_default()
+            )),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => CompressedEncoder::Zstd(zstd::Encoder::new(file,
+                level.map(|level| level as i32).unwrap_or(zstd::DEFAULT_COMPRESSION_LEVEL)
+            )?)
+        });
+    }
+    /// Compress some data.
+    fn write_all(&mut self, data : &[u8]) -> io::Result<()> {
+        return match (self) {
+            CompressedEncoder::Gzip(encoder) => encoder.write_all(data),
+            #[cfg(feature = "zstd")]
+            CompressedEncoder::Zstd(encoder) => encoder.write_all(data)
+        };
+    }
+    /// Write the stream trailer.
+    fn finish(self) -> io::Result<()> {
+        return match (self) {
+            CompressedEncoder::Gzip(encoder) => encoder.finish().map(drop),
+            #[cfg(feature = "zstd")]
+            CompressedEncoder::Zstd(encoder) => encoder.finish().map(drop)
+        };
+    }
+}
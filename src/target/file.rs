@@ -0,0 +1,246 @@
+//! A target that writes to a file.
+
+
+
+use std::fs;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::LogContext;
+use crate::format::{logfmt, Formatter};
+use crate::target::{Target, TargetError};
+
+
+
+/// When a `File` target flushes the
+/// messages that it has buffered.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FlushPolicy {
+    /// Flush after every message.
+    Always,
+    /// Flush when the buffer is full, or
+    /// when `Target::flush` is called.
+    /// 
+    /// Anything still buffered when the
+    /// process exits is lost, unless
+    /// `loggerithm::shutdown` is called.
+    Buffered
+}
+
+/// When a `File` target asks the
+/// operating system to write its
+/// data to the disk.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SyncPolicy {
+    /// Never sync. The operating system
+    /// decides when to write.
+    Never,
+    /// Sync every time the target flushes.
+    OnFlush
+}
+
+
+
+/// Options used to open a `File` target.
+/// 
+/// # Examples
+/// 
+/// ```no_run
+/// use loggerithm::target::{FileOptions, FlushPolicy, SyncPolicy};
+/// let file = FileOptions::new()
+///     .set_append(false)
+///     .set_flush_policy(FlushPolicy::Buffered)
+///     .set_sync_policy(SyncPolicy::OnFlush)
+///     .open("logs/app.log")
+///     .unwrap();
+/// ```
+pub struct FileOptions {
+    append       : bool,
+    create_dirs  : bool,
+    flush_policy : FlushPolicy,
+    sync_policy  : SyncPolicy,
+    formatter    : Box<dyn Formatter>
+}
+/// Initialisation.
+impl FileOptions {
+    /// Create the default options.
+    /// 
+    /// By default, the file is appended to,
+    /// missing parent directories are created,
+    /// and every message is flushed but not
+    /// synced.
+    pub fn new() -> FileOptions {
+        return FileOptions {
+            append       : true,
+            create_dirs  : true,
+            flush_policy : FlushPolicy::Always,
+            sync_policy  : SyncPolicy::Never,
+            formatter    : Box::new(plain)
+        };
+    }
+    /// Sets whether to append to the file
+    /// instead of truncating it when opened.
+    pub fn set_append(mut self, append : bool) -> FileOptions {
+        self.append = append;
+        return self;
+    }
+    /// Sets whether to create any missing
+    /// parent directories when opened.
+    pub fn set_create_dirs(mut self, create_dirs : bool) -> FileOptions {
+        self.create_dirs = create_dirs;
+        return self;
+    }
+    /// Sets when buffered messages are flushed.
+    pub fn set_flush_policy(mut self, flush_policy : FlushPolicy) -> FileOptions {
+        self.flush_policy = flush_policy;
+        return self;
+    }
+    /// Sets when flushed messages are synced
+    /// to the disk.
+    pub fn set_sync_policy(mut self, sync_policy : SyncPolicy) -> FileOptions {
+        self.sync_policy = sync_policy;
+        return self;
+    }
+    /// Sets the formatter that turns a log
+    /// message into a line of text.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `formatter` - The formatter, such as `format::Json`,
+    ///                 or a function that takes a
+    ///                 `&LogContext`. The returned line
+    ///                 should not end in a newline.
+    /// 
+    /// # Generics
+    /// 
+    /// * `F` - The formatter.
+    pub fn set_formatter<F : 'static>(mut self, formatter : F) -> FileOptions
+        where F : Formatter
+    {
+        self.formatter = Box::new(formatter);
+        return self;
+    }
+    /// Open the file.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `path` - The path of the file to write to.
+    /// 
+    /// # Returns
+    /// 
+    /// The file target, or the error that occured
+    /// while opening it.
+    pub fn open<P>(self, path : P) -> io::Result<File>
+        where P : AsRef<Path>
+    {
+        let path = path.as_ref().to_path_buf();
+        let file = open_file(&path, self.append, self.create_dirs)?;
+        return Ok(File {
+            path,
+            writer       : Mutex::new(BufWriter::new(file)),
+            flush_policy : self.flush_policy,
+            sync_policy  : self.sync_policy,
+            formatter    : self.formatter
+        });
+    }
+}
+
+
+
+/// A target that writes each log message
+/// to a file as a line of text.
+/// 
+/// The file is opened once, and can safely
+/// be written to from multiple threads.
+pub struct File {
+    path         : PathBuf,
+    writer       : Mutex<BufWriter<fs::File>>,
+    flush_policy : FlushPolicy,
+    sync_policy  : SyncPolicy,
+    formatter    : Box<dyn Formatter>
+}
+/// Initialisation.
+impl File {
+    /// Open a file with the default options.
+    /// 
+    /// See `FileOptions::new`.
+    pub fn open<P>(path : P) -> io::Result<File>
+        where P : AsRef<Path>
+    {
+        return FileOptions::new().open(path);
+    }
+    /// Create the default options, used to
+    /// open a file with custom options.
+    pub fn options() -> FileOptions {
+        return FileOptions::new();
+    }
+}
+/// Data access functions.
+impl File {
+    /// Get the path of the file.
+    pub fn path(&self) -> &Path {
+        return &self.path;
+    }
+}
+impl Target for File {
+    fn write(&self, context : &LogContext) -> Result<(), TargetError> {
+        let line = self.formatter.format(context);
+        let mut writer = self.writer.lock().unwrap_or_else(|error| error.into_inner());
+        writeln!(writer, "{}", line)?;
+        if (self.flush_policy == FlushPolicy::Always) {
+            flush_writer(&mut writer, self.sync_policy)?;
+        }
+        return Ok(());
+    }
+    fn flush(&self) -> Result<(), TargetError> {
+        let mut writer = self.writer.lock().unwrap_or_else(|error| error.into_inner());
+        flush_writer(&mut writer, self.sync_policy)?;
+        return Ok(());
+    }
+}
+
+
+
+/// Open a file for writing, creating
+/// parent directories if requested.
+pub(crate) fn open_file(path : &Path, append : bool, create_dirs : bool) -> io::Result<fs::File> {
+    if (create_dirs) {
+        if let Some(parent) = path.parent() {
+            if (! parent.as_os_str().is_empty()) {
+                fs::create_dir_all(parent)?;
+            }
+        }
+    }
+    return fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(append)
+        .truncate(! append)
+        .open(path);
+}
+
+/// Flush a buffered file, then sync it
+/// if requested.
+pub(crate) fn flush_writer(writer : &mut BufWriter<fs::File>, sync_policy : SyncPolicy) -> io::Result<()> {
+    writer.flush()?;
+    if (sync_policy == SyncPolicy::OnFlush) {
+        writer.get_ref().sync_data()?;
+    }
+    return Ok(());
+}
+
+/// The default layout of a line, similar
+/// to `Logger::default()` but without any
+/// colours. Structured fields are written
+/// after the message.
+pub(crate) fn plain(context : &LogContext) -> String {
+    return format!(
+        "[ {} ] [ {} ] [ {} ] {}{}",
+        context.time_local().format("%Y-%m-%d %H:%M:%S.%f"),
+        context.module_p(),
+        context.level_name_p(),
+        context.message(),
+        logfmt::fields(context)
+    );
+}
@@ -0,0 +1,148 @@
+//! A target that only writes some log
+//! messages to another target.
+
+
+
+use crate::LogContext;
+use crate::filter::Filter;
+use crate::target::{Target, TargetError};
+
+
+
+/// A target that writes the log messages
+/// that pass its minimum severity and
+/// filter to another target.
+/// 
+/// This lets one logger write different
+/// messages to each of its targets.
+/// 
+/// # Examples
+/// 
+/// ```no_run
+/// # use loggerithm::{logger, log};
+/// # use loggerithm::logger::Logger;
+/// # use loggerithm::level::{TRACE, INFO};
+/// use loggerithm::target::{Filtered, Stdout, File};
+/// logger!(Logger::new()
+///     .set_min_severity(TRACE::SEVERITY)
+///     .with_target(Filtered::new(Stdout::new())
+///         .set_min_severity(INFO::SEVERITY)
+///     )
+///     .with_target(File::open("logs/trace.log").unwrap())
+/// );
+/// # fn main() {
+/// log!(TRACE, "This is only written to `logs/trace.log`.");
+/// log!(INFO, "This is written to both.");
+/// # }
+/// ```
+pub struct Filtered {
+    target       : Box<dyn Target>,
+    min_severity : u32,
+    filter       : Option<Filter>
+}
+/// Initialisation.
+impl Filtered {
+    /// Wrap a target, without filtering
+    /// anything.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `target` - The target to write to.
+    pub fn new<T : 'static>(target : T) -> Filtered
+        where T : Target
+    {
+        return Filtered {
+            target       : Box::new(target),
+            min_severity : 0,
+            filter       : None
+        };
+    }
+    /// Sets the minimum severity index
+    /// required for a message to be written
+    /// to the target.
+    /// 
+    /// This is checked after the minimum
+    /// severity of the logger.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// # use loggerithm::logger::Logger;
+    /// # use loggerithm::level::{DEBUG, INFO};
+    /// use loggerithm::target::{Filtered, Stdout};
+    /// let logger = Logger::new()
+    ///     .with_target(Filtered::new(Stdout::new())
+    ///         .set_min_severity(INFO::SEVERITY)
+    ///     );
+    /// // No target wants `DEBUG` messages, so they are not formatted.
+    /// assert!(! logger.enabled(&DEBUG::LEVEL, "app"));
+    /// assert!(logger.enabled(&INFO::LEVEL, "app"));
+    /// ```
+    pub fn set_min_severity<F>(mut self, min_severity : F) -> Filtered
+        where F : Fn() -> u32
+    {
+        self.min_severity = min_severity();
+        return self;
+    }
+    /// Sets the filter that decides if a
+    /// message is written to the target,
+    /// replacing any previous filter.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `filter` - A `filter::Filter`, or a function
+    ///              that takes a `&LogContext`, and
+    ///              returns `true` to write the message.
+    /// 
+    /// # Generics
+    /// 
+    /// * `F` - The filter.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// # use loggerithm::LogContext;
+    /// use loggerithm::target::{Filtered, Stderr};
+    /// let target = Filtered::new(Stderr::new())
+    ///     .set_filter(|context : &LogContext| context.module().starts_with("app::db"));
+    /// ```
+    pub fn set_filter<F>(mut self, filter : F) -> Filtered
+        where F : Into<Filter>
+    {
+        self.filter = Some(filter.into());
+        return self;
+    }
+}
+impl Target for Filtered {
+    fn write(&self, context : &LogContext) -> Result<(), TargetError> {
+        return self.target.write(context);
+    }
+    fn flush(&self) -> Result<(), TargetError> {
+        return self.target.flush();
+    }
+    fn close(&self) -> Result<(), TargetError> {
+        return self.target.close();
+    }
+    fn enabled(&self, context : &LogContext) -> bool {
+        if let Some(filter) = &self.filter {
+            if (! filter.matches(context)) {
+                return false;
+            }
+        }
+        return self.target.enabled(context);
+    }
+    fn enabled_for(&self, severity : u32, module : &str) -> Option<bool> {
+        let filter = match (&self.filter) {
+            Some(filter) => filter.matches_metadata(severity, module),
+            None         => Some(true)
+        };
+        return match (filter, self.target.enabled_for(severity, module)) {
+            (Some(false), _) | (_, Some(false)) => Some(false),
+            (Some(true), Some(true))            => Some(true),
+            _                                   => None
+        };
+    }
+    fn min_severity(&self) -> u32 {
+        return self.min_severity.max(self.target.min_severity());
+    }
+}
@@ -0,0 +1,394 @@
+//! A target that writes to a file, and
+//! rotates it when it gets too large or
+//! too old.
+
+
+
+use std::fs;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use chrono::format::{self as chrono_format, Parsed, StrftimeItems};
+
+use crate::LogContext;
+use crate::format::Formatter;
+use crate::target::{Target, TargetError};
+use crate::target::file::{self, FlushPolicy, SyncPolicy};
+
+
+
+/// When a `RotatingFile` target rotates
+/// based on the time of the log message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rotation {
+    /// Never rotate based on time.
+    Never,
+    /// Rotate at the start of every hour.
+    Hourly,
+    /// Rotate at the start of every day.
+    Daily
+}
+
+/// How archived files are named.
+/// 
+/// For a file at `logs/app.log`:
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Naming {
+    /// `logs/app.log.1`, `logs/app.log.2`, ...
+    /// 
+    /// The most recent archive is always
+    /// numbered `1`.
+    Numbered,
+    /// `logs/app.2026-10-18.log`, ...
+    /// 
+    /// The value is a `chrono` format string,
+    /// which is given the time that the archived
+    /// file was started.
+    Dated(String)
+}
+
+
+
+/// Options used to open a `RotatingFile` target.
+/// 
+/// # Examples
+/// 
+/// ```no_run
+/// use loggerithm::target::{RotatingFileOptions, Rotation, Naming};
+/// let file = RotatingFileOptions::new()
+///     .set_max_size(10 * 1024 * 1024)
+///     .set_rotation(Rotation::Daily)
+///     .set_naming(Naming::Dated(String::from("%Y-%m-%d")))
+///     .set_max_archives(7)
+///     .open("logs/app.log")
+///     .unwrap();
+/// ```
+pub struct RotatingFileOptions {
+    max_size     : Option<u64>,
+    rotation     : Rotation,
+    naming       : Naming,
+    max_archives : Option<usize>,
+    create_dirs  : bool,
+    flush_policy : FlushPolicy,
+    sync_policy  : SyncPolicy,
+    formatter    : Box<dyn Formatter>
+}
+/// Initialisation.
+impl RotatingFileOptions {
+    /// Create the default options.
+    /// 
+    /// By default, the file never rotates,
+    /// archives are numbered and all of them
+    /// are kept. Directories, flushing and
+    /// syncing are the same as `FileOptions::new`.
+    pub fn new() -> RotatingFileOptions {
+        return RotatingFileOptions {
+            max_size     : None,
+            rotation     : Rotation::Never,
+            naming       : Naming::Numbered,
+            max_archives : None,
+            create_dirs  : true,
+            flush_policy : FlushPolicy::Always,
+            sync_policy  : SyncPolicy::Never,
+            formatter    : Box::new(file::plain)
+        };
+    }
+    /// Sets the size in bytes that the file
+    /// can reach before it is rotated.
+    pub fn set_max_size(mut self, max_size : u64) -> RotatingFileOptions {
+        self.max_size = Some(max_size);
+        return self;
+    }
+    /// Sets when the file is rotated based
+    /// on the time of the log message.
+    pub fn set_rotation(mut self, rotation : Rotation) -> RotatingFileOptions {
+        self.rotation = rotation;
+        return self;
+    }
+    /// Sets how archived files are named.
+    pub fn set_naming(mut self, naming : Naming) -> RotatingFileOptions {
+        self.naming = naming;
+        return self;
+    }
+    /// Sets the number of archived files to
+    /// keep. Older archives are deleted.
+    /// 
+    /// With `Naming::Dated`, only files whose
+    /// names match the date format are counted.
+    pub fn set_max_archives(mut self, max_archives : usize) -> RotatingFileOptions {
+        self.max_archives = Some(max_archives);
+        return self;
+    }
+    /// Sets whether to create any missing
+    /// parent directories when opened.
+    pub fn set_create_dirs(mut self, create_dirs : bool) -> RotatingFileOptions {
+        self.create_dirs = create_dirs;
+        return self;
+    }
+    /// Sets when buffered messages are flushed.
+    pub fn set_flush_policy(mut self, flush_policy : FlushPolicy) -> RotatingFileOptions {
+        self.flush_policy = flush_policy;
+        return self;
+    }
+    /// Sets when flushed messages are synced
+    /// to the disk.
+    pub fn set_sync_policy(mut self, sync_policy : SyncPolicy) -> RotatingFileOptions {
+        self.sync_policy = sync_policy;
+        return self;
+    }
+    /// Sets the formatter that turns a log
+    /// message into a line of text.
+    /// 
+    /// See `FileOptions::set_formatter`.
+    pub fn set_formatter<F : 'static>(mut self, formatter : F) -> RotatingFileOptions
+        where F : Formatter
+    {
+        self.formatter = Box::new(formatter);
+        return self;
+    }
+    /// Open the file.
+    /// 
+    /// If the file already exists, it is
+    /// appended to.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `path` - The path of the file to write to.
+    ///            Archives are placed next to it.
+    /// 
+    /// # Returns
+    /// 
+    /// The file target, or the error that occured
+    /// while opening it.
+    pub fn open<P>(self, path : P) -> io::Result<RotatingFile>
+        where P : AsRef<Path>
+    {
+        let path   = path.as_ref().to_path_buf();
+        let writer = file::open_file(&path, true, self.create_dirs)?;
+        let meta   = writer.metadata()?;
+        let start  = if (meta.len() > 0) {
+            meta.modified().map(DateTime::from).unwrap_or_else(|_| Utc::now())
+        } else {Utc::now()};
+        return Ok(RotatingFile {
+            path,
+            max_size     : self.max_size,
+            rotation     : self.rotation,
+            naming       : self.naming,
+            max_archives : self.max_archives,
+            flush_policy : self.flush_policy,
+            sync_policy  : self.sync_policy,
+            formatter    : self.formatter,
+            state        : Mutex::new(RotatingState {
+                writer : Some(BufWriter::new(writer)),
+                size   : meta.len(),
+                start
+            })
+        });
+    }
+}
+
+
+
+/// A target that writes each log message
+/// to a file as a line of text, and moves
+/// it to an archive when it gets too large
+/// or a new hour or day starts.
+pub struct RotatingFile {
+    path         : PathBuf,
+    max_size     : Option<u64>,
+    rotation     : Rotation,
+    naming       : Naming,
+    max_archives : Option<usize>,
+    flush_policy : FlushPolicy,
+    sync_policy  : SyncPolicy,
+    formatter    : Box<dyn Formatter>,
+    state        : Mutex<RotatingState>
+}
+/// The currently open file.
+struct RotatingState {
+    writer : Option<BufWriter<fs::File>>,
+    size   : u64,
+    start  : DateTime<Utc>
+}
+/// Initialisation.
+impl RotatingFile {
+    /// Open a file with the default options.
+    /// 
+    /// See `RotatingFileOptions::new`.
+    pub fn open<P>(path : P) -> io::Result<RotatingFile>
+        where P : AsRef<Path>
+    {
+        return RotatingFileOptions::new().open(path);
+    }
+    /// Create the default options, used to
+    /// open a file with custom options.
+    pub fn options() -> RotatingFileOptions {
+        return RotatingFileOptions::new();
+    }
+}
+/// Data access functions.
+impl RotatingFile {
+    /// Get the path of the file currently
+    /// being written to.
+    pub fn path(&self) -> &Path {
+        return &self.path;
+    }
+}
+impl RotatingFile {
+    /// Check if the file should be rotated
+    /// before a line is written to it.
+    fn should_rotate(&self, state : &RotatingState, time : DateTime<Utc>, len : u64) -> bool {
+        if let Some(max_size) = self.max_size {
+            if (state.size > 0 && state.size + len > max_size) {
+                return true;
+            }
+        }
+        let period = match (self.rotation) {
+            Rotation::Never  => return false,
+            Rotation::Hourly => "%Y-%m-%d %H",
+            Rotation::Daily  => "%Y-%m-%d"
+        };
+        return state.start.format(period).to_string() != time.format(period).to_string();
+    }
+    /// Archive the current file, delete old
+    /// archives, then start a new file.
+    fn rotate(&self, state : &mut RotatingState, time : DateTime<Utc>) -> io::Result<()> {
+        if let Some(mut writer) = state.writer.take() {
+            file::flush_writer(&mut writer, self.sync_policy)?;
+        }
+        match (&self.naming) {
+            Naming::Numbered      => self.rotate_numbered()?,
+            Naming::Dated(format) => self.rotate_dated(format, state.start)?
+        }
+        state.writer = Some(BufWriter::new(file::open_file(&self.path, true, false)?));
+        state.size   = 0;
+        state.start  = time;
+        return Ok(());
+    }
+    /// Shift every numbered archive up by
+    /// one, then archive the current file
+    /// as `1`.
+    fn rotate_numbered(&self) -> io::Result<()> {
+        let mut last = 0;
+        while (self.numbered(last + 1).exists()) {
+            last += 1;
+        }
+        if let Some(max_archives) = self.max_archives {
+            while (last >= max_archives && last > 0) {
+                fs::remove_file(self.numbered(last))?;
+                last -= 1;
+            }
+            if (max_archives == 0) {
+                return fs::remove_file(&self.path);
+            }
+        }
+        for index in (1..=last).rev() {
+            fs::rename(self.numbered(index), self.numbered(index + 1))?;
+        }
+        return fs::rename(&self.path, self.numbered(1));
+    }
+    /// Archive the current file under the
+    /// time that it was started, then delete
+    /// the oldest archives.
+    fn rotate_dated(&self, format : &str, start : DateTime<Utc>) -> io::Result<()> {
+        let (stem, extension) = self.split_name();
+        let date   = start.format(format).to_string();
+        let mut archive = self.path.with_file_name(format!("{}.{}{}", stem, date, extension));
+        let mut index   = 1;
+        while (archive.exists()) {
+            archive = self.path.with_file_name(format!("{}.{}.{}{}", stem, date, index, extension));
+            index  += 1;
+        }
+        fs::rename(&self.path, &archive)?;
+        if let Some(max_archives) = self.max_archives {
+            let mut archives = Vec::new();
+            for entry in fs::read_dir(self.directory())? {
+                let entry = entry?;
+                let name  = entry.file_name().to_string_lossy().to_string();
+                let middle = name.strip_prefix(&format!("{}.", stem))
+                    .and_then(|name| name.strip_suffix(&extension));
+                if (middle.is_some_and(|middle| is_dated(middle, format))) {
+                    archives.push((entry.metadata()?.modified()?, entry.path()));
+                }
+            }
+            archives.sort();
+            let excess = archives.len().saturating_sub(max_archives);
+            for (_, path) in archives.into_iter().take(excess) {
+                fs::remove_file(path)?;
+            }
+        }
+        return Ok(());
+    }
+    /// Get the path of a numbered archive.
+    fn numbered(&self, index : usize) -> PathBuf {
+        let mut name = self.path.file_name().unwrap_or_default().to_os_string();
+        name.push(format!(".{}", index));
+        return self.path.with_file_name(name);
+    }
+    /// Split the file name into the part
+    /// before the extension, and the extension
+    /// including the `.`.
+    fn split_name(&self) -> (String, String) {
+        let stem      = self.path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        let extension = match (self.path.extension()) {
+            Some(extension) => format!(".{}", extension.to_string_lossy()),
+            None            => String::new()
+        };
+        return (stem, extension);
+    }
+    /// Get the directory that the file is in.
+    fn directory(&self) -> PathBuf {
+        return match (self.path.parent()) {
+            Some(parent) if (! parent.as_os_str().is_empty()) => parent.to_path_buf(),
+            _ => PathBuf::from(".")
+        };
+    }
+}
+/// Check if the middle part of an archive
+/// name is a date in the given format,
+/// optionally followed by a `.` and an index.
+fn is_dated(middle : &str, format : &str) -> bool {
+    let parses = |date : &str| chrono_format::parse(&mut Parsed::new(), date, StrftimeItems::new(format)).is_ok();
+    if (parses(middle)) {
+        return true;
+    }
+    return match (middle.rsplit_once('.')) {
+        Some((date, index)) => index.parse::<usize>().is_ok() && parses(date),
+        None                => false
+    };
+}
+impl Target for RotatingFile {
+    fn write(&self, context : &LogContext) -> Result<(), TargetError> {
+        let line       = format!("{}\n", self.formatter.format(context));
+        let time       = context.time_utc();
+        let mut state  = self.state.lock().unwrap_or_else(|error| error.into_inner());
+        let mut result = Ok(());
+        if (state.writer.is_none()) {
+            match (file::open_file(&self.path, true, false)) {
+                Ok(file)   => state.writer = Some(BufWriter::new(file)),
+                Err(error) => result = Err(error)
+            }
+        } else if (self.should_rotate(&state, time, line.len() as u64)) {
+            result = self.rotate(&mut state, time);
+        }
+        let sync_policy = self.sync_policy;
+        let flush       = self.flush_policy == FlushPolicy::Always;
+        if let Some(writer) = &mut state.writer {
+            writer.write_all(line.as_bytes())?;
+            if (flush) {
+                file::flush_writer(writer, sync_policy)?;
+            }
+            state.size += line.len() as u64;
+        }
+        return result.map_err(TargetError::from);
+    }
+    fn flush(&self) -> Result<(), TargetError> {
+        let mut state = self.state.lock().unwrap_or_else(|error| error.into_inner());
+        if let Some(writer) = &mut state.writer {
+            file::flush_writer(writer, self.sync_policy)?;
+        }
+        return Ok(());
+    }
+}
@@ -0,0 +1,109 @@
+//! Targets that write to the standard
+//! output and error streams.
+
+
+
+use std::io::{self, Write};
+
+use crate::LogContext;
+use crate::format::Formatter;
+use crate::target::{Target, TargetError};
+use crate::target::file;
+
+
+
+/// A target that prints each log message
+/// to the standard output as a line of text.
+/// 
+/// # Examples
+/// 
+/// ```
+/// # use loggerithm::{logger, log};
+/// # use loggerithm::logger::Logger;
+/// # use loggerithm::level::INFO;
+/// use loggerithm::format::Pattern;
+/// use loggerithm::target::Stdout;
+/// logger!(Logger::new()
+///     .with_target(Stdout::new()
+///         .set_formatter(Pattern::new("{time:%H:%M:%S} {level:pf} {module:p}:{line} {message}").unwrap())
+///     )
+/// );
+/// # fn main() {
+/// log!(INFO, "Hello!");
+/// # }
+/// ```
+pub struct Stdout {
+    formatter : Box<dyn Formatter>
+}
+/// Initialisation.
+impl Stdout {
+    /// Create a new target that uses the
+    /// same layout as `target::File`.
+    pub fn new() -> Stdout {
+        return Stdout {
+            formatter : Box::new(file::plain)
+        };
+    }
+    /// Sets the formatter that turns a log
+    /// message into a line of text.
+    /// 
+    /// See `FileOptions::set_formatter`.
+    pub fn set_formatter<F : 'static>(mut self, formatter : F) -> Stdout
+        where F : Formatter
+    {
+        self.formatter = Box::new(formatter);
+        return self;
+    }
+}
+impl Target for Stdout {
+    fn write(&self, context : &LogContext) -> Result<(), TargetError> {
+        let line = self.formatter.format(context);
+        writeln!(io::stdout().lock(), "{}", line)?;
+        return Ok(());
+    }
+    fn flush(&self) -> Result<(), TargetError> {
+        io::stdout().flush()?;
+        return Ok(());
+    }
+}
+
+
+
+/// A target that prints each log message
+/// to the standard error as a line of text.
+/// 
+/// See `Stdout`.
+pub struct Stderr {
+    formatter : Box<dyn Formatter>
+}
+/// Initialisation.
+impl Stderr {
+    /// Create a new target that uses the
+    /// same layout as `target::File`.
+    pub fn new() -> Stderr {
+        return Stderr {
+            formatter : Box::new(file::plain)
+        };
+    }
+    /// Sets the formatter that turns a log
+    /// message into a line of text.
+    /// 
+    /// See `FileOptions::set_formatter`.
+    pub fn set_formatter<F : 'static>(mut self, formatter : F) -> Stderr
+        where F : Formatter
+    {
+        self.formatter = Box::new(formatter);
+        return self;
+    }
+}
+impl Target for Stderr {
+    fn write(&self, context : &LogContext) -> Result<(), TargetError> {
+        let line = self.formatter.format(context);
+        writeln!(io::stderr().lock(), "{}", line)?;
+        return Ok(());
+    }
+    fn flush(&self) -> Result<(), TargetError> {
+        io::stderr().flush()?;
+        return Ok(());
+    }
+}
@@ -0,0 +1,150 @@
+use std::sync::{Arc, Condvar, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+use loggerithm::LogContext;
+use loggerithm::logger::Logger;
+use loggerithm::level::INFO;
+use loggerithm::target::{Target, TargetError, Async, Overflow};
+
+/// A target that records messages, and
+/// waits for its gate to open before
+/// writing each one.
+struct Gated {
+    messages : Arc<Mutex<Vec<String>>>,
+    open     : Arc<(Mutex<bool>, Condvar)>,
+    entered  : Sender<()>
+}
+impl Target for Gated {
+    fn write(&self, context : &LogContext) -> Result<(), TargetError> {
+        let _ = self.entered.send(());
+        let (open, opened) = &*self.open;
+        let _open = opened.wait_while(open.lock().unwrap(), |open| ! *open).unwrap();
+        self.messages.lock().unwrap().push(context.message());
+        Ok(())
+    }
+}
+
+/// The test side of a `Gated` target.
+struct Gate {
+    messages : Arc<Mutex<Vec<String>>>,
+    open     : Arc<(Mutex<bool>, Condvar)>,
+    entered  : Receiver<()>
+}
+impl Gate {
+    /// Let every message through.
+    fn open(&self) {
+        let (open, opened) = &*self.open;
+        *open.lock().unwrap() = true;
+        opened.notify_all();
+    }
+    fn messages(&self) -> Vec<String> {
+        self.messages.lock().unwrap().clone()
+    }
+}
+
+/// Wrap a `Gated` target, with the gate
+/// closed.
+fn wrap(capacity : usize, overflow : Overflow) -> (Async, Gate) {
+    let messages = Arc::new(Mutex::new(Vec::new()));
+    let open     = Arc::new((Mutex::new(false), Condvar::new()));
+    let (sender, entered) = mpsc::channel();
+    let target = Async::options()
+        .set_capacity(capacity)
+        .set_overflow(overflow)
+        .wrap(Gated {messages : Arc::clone(&messages), open : Arc::clone(&open), entered : sender})
+        .unwrap();
+    (target, Gate {messages, open, entered})
+}
+
+fn write(target : &Async, message : &str) {
+    let logger  = Logger::new();
+    let context = logger.create_context(&INFO::LEVEL, String::from("async"), (0, 0), String::from(message));
+    target.write(&context).unwrap();
+}
+
+/// Write a message, and wait until the
+/// background thread is stuck writing it,
+/// so that the queue is empty.
+fn write_first(target : &Async, gate : &Gate) {
+    write(target, "0");
+    gate.entered.recv().unwrap();
+}
+
+#[test]
+fn drop_newest_drops_new_messages_and_reports_them() {
+    let (target, gate) = wrap(2, Overflow::DropNewest);
+    write_first(&target, &gate);
+    for message in ["1", "2", "3", "4"] {
+        write(&target, message);
+    }
+    assert_eq!(target.dropped(), 2);
+    gate.open();
+    target.close().unwrap();
+    assert_eq!(gate.messages(), ["0", "1", "2", "Dropped 2 messages because the queue was full."]);
+}
+
+#[test]
+fn drop_newest_reports_before_the_next_message() {
+    let (target, gate) = wrap(1, Overflow::DropNewest);
+    write_first(&target, &gate);
+    write(&target, "1");
+    write(&target, "2");
+    gate.open();
+    target.flush().unwrap();
+    write(&target, "3");
+    target.close().unwrap();
+    assert_eq!(gate.messages(), ["0", "1", "Dropped 1 message because the queue was full.", "3"]);
+}
+
+#[test]
+fn drop_oldest_replaces_old_messages_with_one_report() {
+    let (target, gate) = wrap(2, Overflow::DropOldest);
+    write_first(&target, &gate);
+    for message in ["1", "2", "3", "4", "5"] {
+        write(&target, message);
+    }
+    assert_eq!(target.dropped(), 3);
+    gate.open();
+    target.close().unwrap();
+    assert_eq!(gate.messages(), ["0", "Dropped 3 messages because the queue was full.", "4", "5"]);
+}
+
+#[test]
+fn drop_oldest_reports_do_not_fill_the_queue() {
+    let (target, gate) = wrap(1, Overflow::DropOldest);
+    write_first(&target, &gate);
+    for index in 1..100 {
+        write(&target, &index.to_string());
+    }
+    assert_eq!(target.dropped(), 98);
+    gate.open();
+    target.close().unwrap();
+    assert_eq!(gate.messages(), ["0", "Dropped 98 messages because the queue was full.", "99"]);
+}
+
+#[test]
+fn block_waits_for_space_in_the_queue() {
+    let (target, gate) = wrap(1, Overflow::Block);
+    let target = Arc::new(target);
+    write_first(&target, &gate);
+    write(&target, "1");
+    let written = Arc::new(AtomicBool::new(false));
+    let blocked = {
+        let target  = Arc::clone(&target);
+        let written = Arc::clone(&written);
+        thread::spawn(move || {
+            write(&target, "2");
+            written.store(true, Ordering::Relaxed);
+        })
+    };
+    thread::sleep(Duration::from_millis(100));
+    assert!(! written.load(Ordering::Relaxed));
+    gate.open();
+    blocked.join().unwrap();
+    target.close().unwrap();
+    assert_eq!(target.dropped(), 0);
+    assert_eq!(gate.messages(), ["0", "1", "2"]);
+}
@@ -0,0 +1,34 @@
+use std::sync::Arc;
+
+use loggerithm::logger::Logger;
+use loggerithm::level::{DEBUG, INFO};
+use loggerithm::internal::{self, Callsite, LoggerLocation};
+
+/// A logger that writes nowhere.
+fn logger() -> Logger {
+    Logger::new().add_target(|_| {})
+}
+
+#[test]
+fn follows_changes_to_loggers_and_severities() {
+    static CALLSITE : Callsite = Callsite::new();
+    internal::register(String::from("callsite"), LoggerLocation::from(logger().set_min_severity(INFO::SEVERITY)));
+    let first = CALLSITE.logger("callsite", INFO::SEVERITY()).unwrap();
+    assert!(CALLSITE.logger("callsite", DEBUG::SEVERITY()).is_none());
+    loggerithm::set_module_min_severity("callsite", DEBUG::SEVERITY);
+    assert!(CALLSITE.logger("callsite", DEBUG::SEVERITY()).is_some());
+    loggerithm::clear_module_min_severity("callsite");
+    assert!(CALLSITE.logger("callsite", DEBUG::SEVERITY()).is_none());
+    internal::register(String::from("callsite"), LoggerLocation::from(logger()));
+    let second = CALLSITE.logger("callsite", DEBUG::SEVERITY()).unwrap();
+    assert!(! Arc::ptr_eq(&first, &second));
+}
+
+#[test]
+fn unregistered_modules_use_the_closest_registered_parent() {
+    internal::register(String::from("walk::a"), LoggerLocation::from(logger()));
+    let parent = internal::find_module_logger("walk::a", true).unwrap();
+    let root   = internal::find_module_logger("", true).unwrap();
+    assert!(Arc::ptr_eq(&internal::find_module_logger("walk::a::b::c", true).unwrap(), &parent));
+    assert!(Arc::ptr_eq(&internal::find_module_logger("walk::b", true).unwrap(), &root));
+}
@@ -0,0 +1,152 @@
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+use flate2::read::{GzDecoder, MultiGzDecoder};
+
+use loggerithm::LogContext;
+use loggerithm::logger::Logger;
+use loggerithm::level::INFO;
+use loggerithm::target::{Target, Compressed, CompressedOptions};
+
+/// Options that write only the message.
+fn options() -> CompressedOptions {
+    Compressed::options()
+        .set_formatter(|context : &LogContext| context.message())
+}
+
+fn write(target : &Compressed, message : &str) {
+    let logger  = Logger::new();
+    let context = logger.create_context(&INFO::LEVEL, String::from("compressed"), (0, 0), String::from(message));
+    target.write(&context).unwrap();
+}
+
+/// Decode every gzip stream in a file.
+fn decode(path : &Path) -> String {
+    let mut text = String::new();
+    MultiGzDecoder::new(fs::File::open(path).unwrap()).read_to_string(&mut text).unwrap();
+    text
+}
+
+/// Decode only the first gzip stream in a file.
+fn decode_first(path : &Path) -> String {
+    let mut text = String::new();
+    GzDecoder::new(fs::File::open(path).unwrap()).read_to_string(&mut text).unwrap();
+    text
+}
+
+#[test]
+fn writes_a_decodable_stream_when_closed() {
+    let directory = tempfile::tempdir().unwrap();
+    let path      = directory.path().join("app.log.gz");
+    let target    = options().open(&path).unwrap();
+    write(&target, "one");
+    write(&target, "two");
+    target.close().unwrap();
+    assert_eq!(decode(&path), "one\ntwo\n");
+}
+
+#[test]
+fn continues_in_a_new_stream_after_a_flush() {
+    let directory = tempfile::tempdir().unwrap();
+    let path      = directory.path().join("app.log.gz");
+    let target    = options().open(&path).unwrap();
+    write(&target, "one");
+    target.flush().unwrap();
+    assert_eq!(decode(&path), "one\n");
+    write(&target, "two");
+    drop(target);
+    assert_eq!(decode(&path), "one\ntwo\n");
+    assert_eq!(decode_first(&path), "one\n");
+}
+
+#[test]
+fn continues_in_a_new_stream_after_the_flush_interval() {
+    let directory = tempfile::tempdir().unwrap();
+    let path      = directory.path().join("app.log.gz");
+    let target    = options()
+        .set_flush_interval(Duration::from_millis(20))
+        .open(&path).unwrap();
+    write(&target, "one");
+    write(&target, "two");
+    thread::sleep(Duration::from_millis(300));
+    // The idle stream has been finished without a flush.
+    assert_eq!(decode(&path), "one\ntwo\n");
+    write(&target, "three");
+    target.close().unwrap();
+    assert_eq!(decode(&path), "one\ntwo\nthree\n");
+    assert_eq!(decode_first(&path), "one\ntwo\n");
+}
+
+#[test]
+fn appends_to_an_existing_file() {
+    let directory = tempfile::tempdir().unwrap();
+    let path      = directory.path().join("app.log.gz");
+    let target    = options().open(&path).unwrap();
+    write(&target, "first run");
+    drop(target);
+    let target = options().open(&path).unwrap();
+    write(&target, "second run");
+    drop(target);
+    assert_eq!(decode(&path), "first run\nsecond run\n");
+}
+
+#[test]
+fn writes_after_close_are_an_error() {
+    let directory = tempfile::tempdir().unwrap();
+    let target    = options().open(directory.path().join("app.log.gz")).unwrap();
+    target.close().unwrap();
+    let logger  = Logger::new();
+    let context = logger.create_context(&INFO::LEVEL, String::from("compressed"), (0, 0), String::from("late"));
+    assert!(target.write(&context).is_err());
+    assert!(target.flush().is_err());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn every_flush_that_loses_messages_is_an_error() {
+    let target = options()
+        .set_create_dirs(false)
+        .open("/dev/full").unwrap();
+    write(&target, "one");
+    assert!(target.flush().is_err());
+    write(&target, "two");
+    assert!(target.flush().is_err());
+    write(&target, "three");
+    assert!(target.close().is_err());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn every_flush_that_loses_messages_is_an_error_without_appending() {
+    let target = options()
+        .set_create_dirs(false)
+        .set_append(false)
+        .open("/dev/full").unwrap();
+    write(&target, "one");
+    assert!(target.flush().is_err());
+    write(&target, "two");
+    assert!(target.flush().is_err());
+    write(&target, "three");
+    assert!(target.close().is_err());
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn writes_decodable_zstd_streams() {
+    use loggerithm::target::Compression;
+    let directory = tempfile::tempdir().unwrap();
+    let path      = directory.path().join("app.log.zst");
+    let target    = options()
+        .set_compression(Compression::Zstd)
+        .open(&path).unwrap();
+    write(&target, "one");
+    target.flush().unwrap();
+    write(&target, "two");
+    target.close().unwrap();
+    let mut text = String::new();
+    zstd::stream::read::Decoder::new(fs::File::open(&path).unwrap()).unwrap().read_to_string(&mut text).unwrap();
+    assert_eq!(text, "one\ntwo\n");
+}
@@ -0,0 +1,69 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+use loggerithm::logger::Logger;
+use loggerithm::level::{ERROR, WARN};
+use loggerithm::internal::{self, Callsite, LoggerLocation};
+
+/// Kept small, so that this can be run with
+/// `MIRIFLAGS=-Zmiri-disable-isolation cargo miri test`,
+/// which is needed to read the time.
+const ROUNDS : usize = if cfg!(miri) {8} else {1000};
+
+/// A logger that counts every message
+/// written by any logger made by it.
+fn logger(written : &Arc<AtomicUsize>) -> Logger {
+    let written = Arc::clone(written);
+    Logger::new().add_target(move |_| {written.fetch_add(1, Ordering::Relaxed);})
+}
+
+#[test]
+fn changes_loggers_and_severities_while_logging() {
+    static CALLSITE : Callsite = Callsite::new();
+    let written = Arc::new(AtomicUsize::new(0));
+    internal::register(String::from("concurrency"), LoggerLocation::from(logger(&written)));
+    internal::register(String::from("concurrency::a"), LoggerLocation::Super);
+
+    let loggers = (0..2).map(|_| {
+        thread::spawn(move || {
+            for _ in 0..ROUNDS {
+                let logger = CALLSITE.logger("concurrency::a", ERROR::SEVERITY()).unwrap();
+                logger.log_args(&ERROR::LEVEL, "concurrency::a", (0, 0), format_args!("logged"));
+            }
+        })
+    }).collect::<Vec<_>>();
+    let registered = {
+        let written = Arc::clone(&written);
+        thread::spawn(move || {
+            for round in 0..ROUNDS {
+                let location = if round % 2 == 0 {LoggerLocation::from(logger(&written))} else {LoggerLocation::Super};
+                internal::register(String::from("concurrency::a"), location);
+            }
+        })
+    };
+    let reloaded = {
+        let written = Arc::clone(&written);
+        thread::spawn(move || {
+            for _ in 0..ROUNDS {
+                loggerithm::reload([("concurrency", logger(&written))]);
+            }
+        })
+    };
+    let severities = thread::spawn(|| {
+        for _ in 0..ROUNDS {
+            loggerithm::set_module_min_severity("concurrency::a", WARN::SEVERITY);
+            loggerithm::clear_module_min_severity("concurrency::a");
+        }
+    });
+    for thread in loggers.into_iter().chain([registered, reloaded, severities]) {
+        thread.join().unwrap();
+    }
+
+    // Every message was written exactly once.
+    assert_eq!(written.load(Ordering::Relaxed), 2 * ROUNDS);
+    // The callsite follows the last change.
+    let current = internal::find_module_logger("concurrency::a", true).unwrap();
+    let cached  = CALLSITE.logger("concurrency::a", ERROR::SEVERITY()).unwrap();
+    assert!(Arc::ptr_eq(&current, &cached));
+}
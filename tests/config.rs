@@ -0,0 +1,29 @@
+#![cfg(feature = "config")]
+
+use std::sync::Arc;
+
+use loggerithm::logger::Logger;
+use loggerithm::internal;
+use loggerithm::config::Config;
+
+fn apply(toml : &str) {
+    Config::from_toml(toml).unwrap().apply().unwrap();
+}
+
+fn logger(module : &str) -> Arc<Logger> {
+    internal::find_module_logger(module, false).unwrap()
+}
+
+#[test]
+fn removing_a_logger_restores_the_original() {
+    internal::register(String::from("config_test"), Logger::new().add_target(|_| {}).into());
+    let original = logger("config_test");
+    apply("[loggers.\"config_test\"]\ntargets = [{ type = \"stderr\" }]");
+    let configured = logger("config_test");
+    assert!(! Arc::ptr_eq(&original, &configured));
+    apply("[loggers.\"config_test\"]\ntargets = [{ type = \"stdout\" }]");
+    assert!(! Arc::ptr_eq(&original, &logger("config_test")));
+    assert!(! Arc::ptr_eq(&configured, &logger("config_test")));
+    apply("");
+    assert!(Arc::ptr_eq(&original, &logger("config_test")));
+}
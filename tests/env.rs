@@ -0,0 +1,27 @@
+use loggerithm::env::{self, EnvError};
+use loggerithm::logger::Logger;
+use loggerithm::level::{DEBUG, WARN};
+use loggerithm::internal::{self, LoggerLocation};
+
+#[test]
+fn rejects_a_misspelled_level() {
+    assert_eq!(env::apply("inof"), Err(EnvError::UnknownDirective {
+        directive : String::from("inof")
+    }));
+}
+
+#[test]
+fn replaces_the_severities_set_by_the_last_call() {
+    internal::register(String::from("env_app::db"), LoggerLocation::from(Logger::new()));
+    env::apply("env_app=warn,env_other=debug").unwrap();
+    assert_eq!(internal::find_severity_override("env_app"), Some(WARN::SEVERITY()));
+    assert_eq!(internal::find_severity_override("env_other"), Some(DEBUG::SEVERITY()));
+    // A bare module with a logger in a submodule.
+    env::apply("env_app").unwrap();
+    assert_eq!(internal::find_severity_override("env_app"), Some(0));
+    assert_eq!(internal::find_severity_override("env_other"), None);
+    // Nothing is changed by an invalid directive.
+    assert!(env::apply("env_other=debug,env_ap").is_err());
+    assert_eq!(internal::find_severity_override("env_app"), Some(0));
+    assert_eq!(internal::find_severity_override("env_other"), None);
+}
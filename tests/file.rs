@@ -0,0 +1,118 @@
+use std::fs;
+
+use loggerithm::LogContext;
+use loggerithm::logger::Logger;
+use loggerithm::level::INFO;
+use loggerithm::target::{Target, TargetError, File, FileOptions, FlushPolicy};
+
+/// Options that write only the message.
+fn options() -> FileOptions {
+    File::options()
+        .set_formatter(|context : &LogContext| context.message())
+}
+
+fn try_write(target : &File, message : &str) -> Result<(), TargetError> {
+    let logger  = Logger::new();
+    let context = logger.create_context(&INFO::LEVEL, String::from("file"), (0, 0), String::from(message));
+    target.write(&context)
+}
+
+fn write(target : &File, message : &str) {
+    try_write(target, message).unwrap();
+}
+
+#[test]
+fn appends_to_an_existing_file() {
+    let directory = tempfile::tempdir().unwrap();
+    let path      = directory.path().join("app.log");
+    fs::write(&path, "first run\n").unwrap();
+    let target = options().open(&path).unwrap();
+    write(&target, "second run");
+    assert_eq!(fs::read_to_string(&path).unwrap(), "first run\nsecond run\n");
+}
+
+#[test]
+fn truncates_an_existing_file() {
+    let directory = tempfile::tempdir().unwrap();
+    let path      = directory.path().join("app.log");
+    fs::write(&path, "first run\n").unwrap();
+    let target = options()
+        .set_append(false)
+        .open(&path).unwrap();
+    write(&target, "second run");
+    assert_eq!(fs::read_to_string(&path).unwrap(), "second run\n");
+}
+
+#[test]
+fn creates_missing_directories() {
+    let directory = tempfile::tempdir().unwrap();
+    let path      = directory.path().join("logs").join("app").join("app.log");
+    let target    = options().open(&path).unwrap();
+    write(&target, "one");
+    assert_eq!(fs::read_to_string(&path).unwrap(), "one\n");
+    assert_eq!(target.path(), path);
+}
+
+#[test]
+fn does_not_create_missing_directories_when_disabled() {
+    let directory = tempfile::tempdir().unwrap();
+    let path      = directory.path().join("logs").join("app.log");
+    assert!(options().set_create_dirs(false).open(&path).is_err());
+    assert!(! directory.path().join("logs").exists());
+}
+
+#[test]
+fn writes_lines_with_the_formatter() {
+    let directory = tempfile::tempdir().unwrap();
+    let path      = directory.path().join("app.log");
+    let target    = File::options()
+        .set_formatter(|context : &LogContext| format!("{} {}", context.level_name(), context.message()))
+        .open(&path).unwrap();
+    write(&target, "one");
+    write(&target, "two");
+    assert_eq!(fs::read_to_string(&path).unwrap(), "INFO one\nINFO two\n");
+}
+
+#[test]
+fn writes_the_plain_layout_by_default() {
+    let directory = tempfile::tempdir().unwrap();
+    let path      = directory.path().join("app.log");
+    let target    = File::open(&path).unwrap();
+    write(&target, "one");
+    let text = fs::read_to_string(&path).unwrap();
+    assert!(text.contains("] [ file"), "{}", text);
+    assert!(text.ends_with("] one\n"), "{}", text);
+}
+
+#[test]
+fn buffers_until_flushed() {
+    let directory = tempfile::tempdir().unwrap();
+    let path      = directory.path().join("app.log");
+    let target    = options()
+        .set_flush_policy(FlushPolicy::Buffered)
+        .open(&path).unwrap();
+    write(&target, "one");
+    assert_eq!(fs::read_to_string(&path).unwrap(), "");
+    target.flush().unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "one\n");
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn reports_write_errors() {
+    let target = options()
+        .set_create_dirs(false)
+        .open("/dev/full").unwrap();
+    assert!(try_write(&target, "one").is_err());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn reports_flush_errors() {
+    let target = options()
+        .set_create_dirs(false)
+        .set_flush_policy(FlushPolicy::Buffered)
+        .open("/dev/full").unwrap();
+    write(&target, "one");
+    assert!(target.flush().is_err());
+}
@@ -0,0 +1,71 @@
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use loggerithm::LogContext;
+use loggerithm::logger::Logger;
+use loggerithm::level::{DEBUG, INFO, WARN};
+use loggerithm::filter::Filter;
+use loggerithm::target::Filtered;
+
+/// A value that counts how many times
+/// it has been formatted.
+struct Counted(Arc<AtomicUsize>);
+impl fmt::Display for Counted {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        self.0.fetch_add(1, Ordering::Relaxed);
+        write!(f, "counted")
+    }
+}
+
+/// Log a message at INFO in a module, with
+/// a filter, and return how many times the
+/// message was formatted and written.
+fn log(module : &str, filter : Filter) -> (usize, usize) {
+    let formatted = Arc::new(AtomicUsize::new(0));
+    let written   = Arc::new(AtomicUsize::new(0));
+    let counter   = Arc::clone(&written);
+    let logger    = Logger::new()
+        .set_filter(filter)
+        .add_target(move |_| {counter.fetch_add(1, Ordering::Relaxed);});
+    logger.log_args(&INFO::LEVEL, module, (0, 0), format_args!("{}", Counted(Arc::clone(&formatted))));
+    (formatted.load(Ordering::Relaxed), written.load(Ordering::Relaxed))
+}
+
+/// The same as `log`, with the filter on
+/// a `Filtered` target instead.
+fn log_filtered(module : &str, filter : Filter) -> (usize, usize) {
+    let formatted = Arc::new(AtomicUsize::new(0));
+    let written   = Arc::new(AtomicUsize::new(0));
+    let counter   = Arc::clone(&written);
+    let logger    = Logger::new()
+        .with_target(Filtered::new(move |_ : &LogContext| {counter.fetch_add(1, Ordering::Relaxed);}).set_filter(filter));
+    logger.log_args(&INFO::LEVEL, module, (0, 0), format_args!("{}", Counted(Arc::clone(&formatted))));
+    (formatted.load(Ordering::Relaxed), written.load(Ordering::Relaxed))
+}
+
+#[test]
+fn module_and_severity_filters_reject_before_formatting() {
+    assert_eq!(log("app::http", Filter::module_prefix("app::db")), (0, 0));
+    assert_eq!(log("app::db", Filter::module_prefix("app::db").not()), (0, 0));
+    assert_eq!(log("app::db", Filter::severity_between(WARN::SEVERITY()..)), (0, 0));
+    assert_eq!(log("app::db", Filter::module_prefix("app::db").and(Filter::severity_between(..DEBUG::SEVERITY()))), (0, 0));
+    assert_eq!(log("app::db", Filter::module_prefix("app::db")), (1, 1));
+}
+
+#[test]
+fn message_filters_are_checked_after_formatting() {
+    assert_eq!(log("app::db", Filter::message_contains("other")), (1, 0));
+    assert_eq!(log("app::db", Filter::message_contains("counted")), (1, 1));
+    assert_eq!(log("app::http", Filter::module_prefix("app::db").and(Filter::message_contains("counted"))), (0, 0));
+    assert_eq!(log("app::http", Filter::module_prefix("app::db").or(Filter::message_contains("counted"))), (1, 1));
+    assert_eq!(log("app::db", Filter::module_prefix("app::db").or(Filter::message_contains("other"))), (1, 1));
+}
+
+#[test]
+fn target_filters_reject_before_formatting() {
+    assert_eq!(log_filtered("app::http", Filter::module_prefix("app::db")), (0, 0));
+    assert_eq!(log_filtered("app::db", Filter::severity_between(WARN::SEVERITY()..)), (0, 0));
+    assert_eq!(log_filtered("app::db", Filter::message_contains("other")), (1, 0));
+    assert_eq!(log_filtered("app::db", Filter::module_prefix("app::db")), (1, 1));
+}
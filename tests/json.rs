@@ -0,0 +1,79 @@
+use chrono::{TimeZone, Utc};
+
+use loggerithm::field::Value;
+use loggerithm::logger::Logger;
+use loggerithm::level::INFO;
+use loggerithm::format::{Formatter, Json};
+
+/// Format a message with some fields,
+/// logged at a fixed time.
+fn format(message : &str, fields : Vec<(&str, Value)>) -> String {
+    let logger      = Logger::new();
+    let mut context = logger.create_context(&INFO::LEVEL, String::from("app::db"), (12, 5), String::from(message));
+    context.set_time(Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap());
+    for (key, value) in fields {
+        context.add_field(key, value);
+    }
+    Json::new().format(&context)
+}
+
+/// The start of every formatted line.
+const START : &str = "{\"time\":\"2026-01-01T12:00:00.000000Z\",\"module\":\"app::db\",\"line\":12,\"column\":5,\"level\":\"INFO\",\"severity\":20,";
+
+#[test]
+fn writes_a_plain_message() {
+    assert_eq!(format("Connected.", vec![]), format!("{}\"message\":\"Connected.\"}}", START));
+}
+
+#[test]
+fn escapes_quotes_and_backslashes() {
+    assert_eq!(
+        format("say \"hi\" to C:\\logs", vec![]),
+        format!("{}\"message\":\"say \\\"hi\\\" to C:\\\\logs\"}}", START)
+    );
+}
+
+#[test]
+fn escapes_newlines_and_tabs() {
+    assert_eq!(
+        format("one\ntwo\r\n\tthree", vec![]),
+        format!("{}\"message\":\"one\\ntwo\\r\\n\\tthree\"}}", START)
+    );
+}
+
+#[test]
+fn escapes_other_control_characters() {
+    assert_eq!(
+        format("bell\u{7} null\u{0} escape\u{1b} delete\u{7f}", vec![]),
+        format!("{}\"message\":\"bell\\u0007 null\\u0000 escape\\u001b delete\\u007f\"}}", START)
+    );
+}
+
+#[test]
+fn keeps_other_unicode() {
+    assert_eq!(format("café ✓", vec![]), format!("{}\"message\":\"café ✓\"}}", START));
+}
+
+#[test]
+fn escapes_field_keys_and_string_values() {
+    assert_eq!(
+        format("m", vec![("a \"key\"", "line\none".into())]),
+        format!("{}\"message\":\"m\",\"fields\":{{\"a \\\"key\\\"\":\"line\\none\"}}}}", START)
+    );
+}
+
+#[test]
+fn writes_field_values_with_their_types() {
+    assert_eq!(
+        format("m", vec![("ok", true.into()), ("count", (-2).into()), ("size", 3u64.into()), ("ratio", 0.5.into()), ("name", "db".into())]),
+        format!("{}\"message\":\"m\",\"fields\":{{\"ok\":true,\"count\":-2,\"size\":3,\"ratio\":0.5,\"name\":\"db\"}}}}", START)
+    );
+}
+
+#[test]
+fn writes_non_finite_numbers_as_null() {
+    assert_eq!(
+        format("m", vec![("nan", f64::NAN.into()), ("inf", f64::INFINITY.into()), ("-inf", f64::NEG_INFINITY.into())]),
+        format!("{}\"message\":\"m\",\"fields\":{{\"nan\":null,\"inf\":null,\"-inf\":null}}}}", START)
+    );
+}
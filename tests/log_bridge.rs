@@ -0,0 +1,32 @@
+#![cfg(feature = "log")]
+
+use std::sync::Mutex;
+
+use loggerithm::logger::Logger;
+use loggerithm::level::WARN;
+use loggerithm::internal;
+
+static MESSAGES : Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn messages() -> Vec<String> {
+    std::mem::take(&mut *MESSAGES.lock().unwrap())
+}
+
+#[test]
+fn uses_the_target_as_the_module() {
+    internal::register(String::from("bridged"), Logger::new()
+        .add_target(|context| MESSAGES.lock().unwrap().push(format!("{} {}", context.module(), context.message())))
+        .into()
+    );
+    loggerithm::bridge::log::init().unwrap();
+    loggerithm::set_module_min_severity("bridged::db", WARN::SEVERITY);
+
+    log::info!(target : "bridged", "logged");
+    log::info!(target : "bridged::db", "below the minimum severity");
+    log::warn!(target : "bridged::db", "logged");
+    assert_eq!(messages(), ["bridged logged", "bridged::db logged"]);
+
+    assert!(log::log_enabled!(target : "bridged::db", log::Level::Warn));
+    assert!(! log::log_enabled!(target : "bridged::db", log::Level::Info));
+    assert!(log::log_enabled!(target : "bridged::http", log::Level::Info));
+}
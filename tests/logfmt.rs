@@ -0,0 +1,90 @@
+use chrono::{TimeZone, Utc};
+
+use loggerithm::field::Value;
+use loggerithm::logger::Logger;
+use loggerithm::level::INFO;
+use loggerithm::format::{Formatter, Logfmt};
+
+/// Format a message with some fields,
+/// logged at a fixed time.
+fn format(message : &str, fields : Vec<(&str, Value)>) -> String {
+    let logger      = Logger::new();
+    let mut context = logger.create_context(&INFO::LEVEL, String::from("app::db"), (12, 5), String::from(message));
+    context.set_time(Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap());
+    for (key, value) in fields {
+        context.add_field(key, value);
+    }
+    Logfmt::new().format(&context)
+}
+
+/// The start of every formatted line.
+const START : &str = "ts=2026-01-01T12:00:00.000000Z level=INFO module=app::db line=12 ";
+
+#[test]
+fn leaves_simple_values_unquoted() {
+    assert_eq!(format("connected", vec![]), format!("{}msg=connected", START));
+}
+
+#[test]
+fn quotes_empty_values() {
+    assert_eq!(
+        format("", vec![("name", "".into())]),
+        format!("{}msg=\"\" name=\"\"", START)
+    );
+}
+
+#[test]
+fn quotes_values_with_spaces_and_equals_signs() {
+    assert_eq!(
+        format("Connected to the database.", vec![("query", "a=1".into())]),
+        format!("{}msg=\"Connected to the database.\" query=\"a=1\"", START)
+    );
+}
+
+#[test]
+fn escapes_quotes_and_backslashes() {
+    assert_eq!(
+        format("say \"hi\"", vec![("path", "C:\\logs".into())]),
+        format!("{}msg=\"say \\\"hi\\\"\" path=\"C:\\\\logs\"", START)
+    );
+}
+
+#[test]
+fn escapes_newlines_and_tabs() {
+    assert_eq!(
+        format("one\ntwo\r\n\tthree", vec![]),
+        format!("{}msg=\"one\\ntwo\\r\\n\\tthree\"", START)
+    );
+}
+
+#[test]
+fn escapes_other_control_characters() {
+    assert_eq!(
+        format("bell\u{7}escape\u{1b}delete\u{7f}", vec![]),
+        format!("{}msg=\"bell\\u0007escape\\u001bdelete\\u007f\"", START)
+    );
+}
+
+#[test]
+fn quotes_keys_when_needed() {
+    assert_eq!(
+        format("m", vec![("a key", 1.into())]),
+        format!("{}msg=m \"a key\"=1", START)
+    );
+}
+
+#[test]
+fn writes_numbers_and_booleans_unquoted() {
+    assert_eq!(
+        format("m", vec![("ok", true.into()), ("count", (-2).into()), ("ratio", 0.5.into())]),
+        format!("{}msg=m ok=true count=-2 ratio=0.5", START)
+    );
+}
+
+#[test]
+fn writes_non_finite_numbers_unquoted() {
+    assert_eq!(
+        format("m", vec![("nan", f64::NAN.into()), ("inf", f64::INFINITY.into()), ("-inf", f64::NEG_INFINITY.into())]),
+        format!("{}msg=m nan=NaN inf=inf -inf=-inf", START)
+    );
+}
@@ -0,0 +1,35 @@
+use chrono::{TimeZone, Utc};
+
+use loggerithm::logger::Logger;
+use loggerithm::level::INFO;
+use loggerithm::format::{Formatter, Pattern, PatternError};
+
+fn format(pattern : &str) -> String {
+    colored::control::set_override(true);
+    let logger  = Logger::new();
+    let mut context = logger.create_context(&INFO::LEVEL, String::from("app"), (12, 5), String::from("hi"));
+    context.set_time(Utc.with_ymd_and_hms(2026, 6, 15, 12, 30, 0).unwrap());
+    Pattern::new(pattern).unwrap().format(&context)
+}
+
+#[test]
+fn applies_colours_and_attributes() {
+    assert_eq!(format("{module|green,dimmed}"), "\u{1b}[2;32mapp\u{1b}[0m");
+    assert_eq!(format("{line|bright green}"), "\u{1b}[92m12\u{1b}[0m");
+    assert_eq!(format("{message|bold,on blue}"), "\u{1b}[1;44mhi\u{1b}[0m");
+}
+
+#[test]
+fn styles_a_time_format() {
+    assert_eq!(format("{time_utc:%Y-%m-%d %H:%M|red}"), "\u{1b}[31m2026-06-15 12:30\u{1b}[0m");
+    // The same month in every time zone.
+    assert_eq!(format("{time:%Y-%m|red,bold}"), "\u{1b}[1;31m2026-06\u{1b}[0m");
+}
+
+#[test]
+fn rejects_unknown_styles() {
+    assert_eq!(Pattern::new("{time}{module|shiny}").unwrap_err(), PatternError::UnknownStyle {
+        style    : String::from("shiny"),
+        position : 6
+    });
+}
@@ -0,0 +1,43 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use loggerithm::LogContext;
+use loggerithm::logger::Logger;
+use loggerithm::internal;
+use loggerithm::target::{Target, TargetError};
+
+/// A target that counts how many times
+/// it has been closed.
+struct Closes(Arc<AtomicUsize>);
+impl Target for Closes {
+    fn write(&self, _context : &LogContext) -> Result<(), TargetError> {
+        Ok(())
+    }
+    fn close(&self) -> Result<(), TargetError> {
+        self.0.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+fn logger(closes : &Arc<AtomicUsize>) -> Logger {
+    Logger::new().with_target(Closes(Arc::clone(closes)))
+}
+
+#[test]
+fn closes_a_replaced_logger_once_it_is_no_longer_used() {
+    let closes = Arc::new(AtomicUsize::new(0));
+    loggerithm::reload([("reload::replaced", logger(&closes))]);
+    let in_use = internal::find_module_logger("reload::replaced", false).unwrap();
+    loggerithm::reload([("reload::replaced", Logger::new())]);
+    assert_eq!(closes.load(Ordering::Relaxed), 0);
+    drop(in_use);
+    assert_eq!(closes.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn closes_a_logger_replaced_by_register() {
+    let closes = Arc::new(AtomicUsize::new(0));
+    internal::register(String::from("reload::registered"), logger(&closes).into());
+    internal::register(String::from("reload::registered"), Logger::new().into());
+    assert_eq!(closes.load(Ordering::Relaxed), 1);
+}
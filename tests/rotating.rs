@@ -0,0 +1,180 @@
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+use chrono::{DateTime, TimeZone, Utc};
+
+use loggerithm::LogContext;
+use loggerithm::logger::Logger;
+use loggerithm::level::INFO;
+use loggerithm::target::{Target, RotatingFile, RotatingFileOptions, Rotation, Naming};
+
+/// Options that write only the message.
+fn options() -> RotatingFileOptions {
+    RotatingFile::options()
+        .set_formatter(|context : &LogContext| context.message())
+}
+
+fn time(year : i32, month : u32, day : u32, hour : u32, minute : u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(year, month, day, hour, minute, 0).unwrap()
+}
+
+/// Write a message logged at a time.
+fn write(target : &RotatingFile, message : &str, time : DateTime<Utc>) {
+    let logger = Logger::new();
+    let mut context = logger.create_context(&INFO::LEVEL, String::from("rotating"), (0, 0), String::from(message));
+    context.set_time(time);
+    target.write(&context).unwrap();
+}
+
+fn read(path : &Path) -> String {
+    fs::read_to_string(path).unwrap()
+}
+
+/// Create a file with some contents, modified at a time.
+fn create(path : &Path, contents : &str, modified : DateTime<Utc>) {
+    fs::write(path, contents).unwrap();
+    let file = fs::File::options().write(true).open(path).unwrap();
+    file.set_modified(SystemTime::from(modified)).unwrap();
+}
+
+/// Get the names of the files in a directory, sorted.
+fn names(directory : &Path) -> Vec<String> {
+    let mut names = fs::read_dir(directory).unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().to_string())
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+#[test]
+fn rotates_at_max_size_and_shifts_numbered_archives() {
+    let directory = tempfile::tempdir().unwrap();
+    let path      = directory.path().join("app.log");
+    let target    = options().set_max_size(10).open(&path).unwrap();
+    let now       = Utc::now();
+    write(&target, "one", now);
+    write(&target, "two", now);
+    assert_eq!(names(directory.path()), ["app.log"]);
+    write(&target, "three", now);
+    write(&target, "four", now);
+    assert_eq!(names(directory.path()), ["app.log", "app.log.1", "app.log.2"]);
+    assert_eq!(read(&directory.path().join("app.log.2")), "one\ntwo\n");
+    assert_eq!(read(&directory.path().join("app.log.1")), "three\n");
+    assert_eq!(read(&path), "four\n");
+}
+
+#[test]
+fn a_message_larger_than_max_size_is_written_to_an_empty_file() {
+    let directory = tempfile::tempdir().unwrap();
+    let path      = directory.path().join("app.log");
+    let target    = options().set_max_size(4).open(&path).unwrap();
+    write(&target, "a long message", Utc::now());
+    assert_eq!(names(directory.path()), ["app.log"]);
+    assert_eq!(read(&path), "a long message\n");
+}
+
+#[test]
+fn keeps_max_archives_numbered_archives() {
+    let directory = tempfile::tempdir().unwrap();
+    let path      = directory.path().join("app.log");
+    let target    = options().set_max_size(1).set_max_archives(2).open(&path).unwrap();
+    let now       = Utc::now();
+    for message in ["1", "2", "3", "4", "5"] {
+        write(&target, message, now);
+    }
+    assert_eq!(names(directory.path()), ["app.log", "app.log.1", "app.log.2"]);
+    assert_eq!(read(&directory.path().join("app.log.2")), "3\n");
+    assert_eq!(read(&directory.path().join("app.log.1")), "4\n");
+    assert_eq!(read(&path), "5\n");
+}
+
+#[test]
+fn zero_max_archives_deletes_the_rotated_file() {
+    let directory = tempfile::tempdir().unwrap();
+    let path      = directory.path().join("app.log");
+    let target    = options().set_max_size(1).set_max_archives(0).open(&path).unwrap();
+    let now       = Utc::now();
+    write(&target, "1", now);
+    write(&target, "2", now);
+    write(&target, "3", now);
+    assert_eq!(names(directory.path()), ["app.log"]);
+    assert_eq!(read(&path), "3\n");
+}
+
+#[test]
+fn rotates_at_the_start_of_an_hour() {
+    let directory = tempfile::tempdir().unwrap();
+    let path      = directory.path().join("app.log");
+    create(&path, "before\n", time(2026, 1, 1, 10, 30));
+    let target = options()
+        .set_rotation(Rotation::Hourly)
+        .set_naming(Naming::Dated(String::from("%Y-%m-%dT%H")))
+        .open(&path).unwrap();
+    write(&target, "10:59", time(2026, 1, 1, 10, 59));
+    assert_eq!(names(directory.path()), ["app.log"]);
+    write(&target, "11:00", time(2026, 1, 1, 11, 0));
+    write(&target, "11:59", time(2026, 1, 1, 11, 59));
+    assert_eq!(names(directory.path()), ["app.2026-01-01T10.log", "app.log"]);
+    assert_eq!(read(&directory.path().join("app.2026-01-01T10.log")), "before\n10:59\n");
+    assert_eq!(read(&path), "11:00\n11:59\n");
+}
+
+#[test]
+fn rotates_at_the_start_of_a_day() {
+    let directory = tempfile::tempdir().unwrap();
+    let path      = directory.path().join("app.log");
+    create(&path, "before\n", time(2026, 1, 1, 22, 0));
+    let target = options()
+        .set_rotation(Rotation::Daily)
+        .set_naming(Naming::Dated(String::from("%Y-%m-%d")))
+        .open(&path).unwrap();
+    write(&target, "23:59", time(2026, 1, 1, 23, 59));
+    write(&target, "00:00", time(2026, 1, 2, 0, 0));
+    write(&target, "next", time(2026, 1, 4, 12, 0));
+    assert_eq!(names(directory.path()), ["app.2026-01-01.log", "app.2026-01-02.log", "app.log"]);
+    assert_eq!(read(&directory.path().join("app.2026-01-01.log")), "before\n23:59\n");
+    assert_eq!(read(&directory.path().join("app.2026-01-02.log")), "00:00\n");
+    assert_eq!(read(&path), "next\n");
+}
+
+#[test]
+fn numbers_dated_archives_with_the_same_name() {
+    let directory = tempfile::tempdir().unwrap();
+    let path      = directory.path().join("app.log");
+    create(&path, "0\n", time(2026, 1, 1, 12, 0));
+    let target = options()
+        .set_max_size(2)
+        .set_naming(Naming::Dated(String::from("%Y-%m-%d")))
+        .open(&path).unwrap();
+    write(&target, "1", time(2026, 1, 1, 12, 0));
+    write(&target, "2", time(2026, 1, 1, 12, 0));
+    write(&target, "3", time(2026, 1, 1, 12, 0));
+    assert_eq!(names(directory.path()), ["app.2026-01-01.1.log", "app.2026-01-01.2.log", "app.2026-01-01.log", "app.log"]);
+    assert_eq!(read(&directory.path().join("app.2026-01-01.log")), "0\n");
+    assert_eq!(read(&directory.path().join("app.2026-01-01.1.log")), "1\n");
+    assert_eq!(read(&directory.path().join("app.2026-01-01.2.log")), "2\n");
+    assert_eq!(read(&path), "3\n");
+}
+
+#[test]
+fn deletes_the_least_recently_modified_dated_archives() {
+    let directory = tempfile::tempdir().unwrap();
+    let path      = directory.path().join("app.log");
+    // Names that sort the opposite way to their times.
+    create(&directory.path().join("app.1999-01-01.log"), "old\n", time(2000, 1, 1, 0, 0));
+    create(&directory.path().join("app.2000-01-01.1.log"), "older\n", time(1990, 1, 1, 0, 0));
+    // Names that are not dated archives.
+    create(&directory.path().join("app.b.log"), "unrelated\n", time(1970, 1, 1, 0, 0));
+    create(&directory.path().join("other.log"), "unrelated\n", time(1980, 1, 1, 0, 0));
+    create(&path, "current\n", time(2026, 1, 1, 12, 0));
+    let target = options()
+        .set_max_size(1)
+        .set_naming(Naming::Dated(String::from("%Y-%m-%d")))
+        .set_max_archives(2)
+        .open(&path).unwrap();
+    write(&target, "new", time(2026, 1, 1, 12, 0));
+    assert_eq!(names(directory.path()), ["app.1999-01-01.log", "app.2026-01-01.log", "app.b.log", "app.log", "other.log"]);
+    assert_eq!(read(&directory.path().join("app.2026-01-01.log")), "current\n");
+    assert_eq!(read(&path), "new\n");
+}
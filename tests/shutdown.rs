@@ -0,0 +1,57 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use loggerithm::LogContext;
+use loggerithm::logger::Logger;
+use loggerithm::level::INFO;
+use loggerithm::internal::{self, Callsite};
+use loggerithm::target::{Target, TargetError};
+
+/// A target that counts how many times
+/// it has been flushed and closed.
+#[derive(Clone, Default)]
+struct Counts {
+    flushes : Arc<AtomicUsize>,
+    closes  : Arc<AtomicUsize>
+}
+impl Counts {
+    fn get(&self) -> (usize, usize) {
+        (self.flushes.load(Ordering::Relaxed), self.closes.load(Ordering::Relaxed))
+    }
+}
+impl Target for Counts {
+    fn write(&self, _context : &LogContext) -> Result<(), TargetError> {
+        Ok(())
+    }
+    fn flush(&self) -> Result<(), TargetError> {
+        self.flushes.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+    fn close(&self) -> Result<(), TargetError> {
+        self.closes.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+#[test]
+fn flushes_and_closes_a_replaced_logger_held_by_a_callsite() {
+    static CALLSITE : Callsite = Callsite::new();
+    let old = Counts::default();
+    let new = Counts::default();
+    internal::register(String::from("shutdown"), Logger::new().with_target(old.clone()).into());
+    let logger = CALLSITE.logger("shutdown", INFO::SEVERITY()).unwrap();
+    logger.log_args(&INFO::LEVEL, "shutdown", (0, 0), format_args!("cached"));
+    drop(logger);
+
+    loggerithm::reload([("shutdown", Logger::new().with_target(new.clone()))]);
+    // The callsite still holds the old logger.
+    assert_eq!(old.get(), (1, 0));
+
+    loggerithm::flush();
+    assert_eq!(old.get(), (2, 0));
+    assert_eq!(new.get(), (1, 0));
+
+    loggerithm::shutdown();
+    assert_eq!(old.get(), (2, 1));
+    assert_eq!(new.get(), (1, 1));
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "tracing")]
+
+use std::sync::Mutex;
+
+use tracing_subscriber::prelude::*;
+
+use loggerithm::logger::Logger;
+use loggerithm::level::WARN;
+use loggerithm::internal;
+use loggerithm::bridge::tracing::TracingLayer;
+
+static MESSAGES : Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn messages() -> Vec<String> {
+    std::mem::take(&mut *MESSAGES.lock().unwrap())
+}
+
+#[test]
+fn uses_the_target_as_the_module() {
+    internal::register(String::from("bridged"), Logger::new()
+        .add_target(|context| MESSAGES.lock().unwrap().push(format!("{} {}", context.module(), context.message())))
+        .into()
+    );
+    let _default = tracing::subscriber::set_default(tracing_subscriber::registry().with(TracingLayer::new()));
+    loggerithm::set_module_min_severity("bridged::db", WARN::SEVERITY);
+
+    tracing::info!(target : "bridged", "logged");
+    tracing::info!(target : "bridged::db", "below the minimum severity");
+    tracing::warn!(target : "bridged::db", "logged");
+    assert_eq!(messages(), ["bridged logged", "bridged::db logged"]);
+}
+
+#[test]
+fn adds_the_event_scope_to_the_spans() {
+    static SPANNED : Mutex<Vec<String>> = Mutex::new(Vec::new());
+    internal::register(String::from("spanned"), Logger::new()
+        .add_target(|context| SPANNED.lock().unwrap().push(format!("{} {}", context.span(), context.message())))
+        .into()
+    );
+    let _default = tracing::subscriber::set_default(tracing_subscriber::registry().with(TracingLayer::new()));
+
+    let outer = tracing::info_span!("outer");
+    let _outer = outer.enter();
+    let inner = tracing::info_span!("inner");
+    let _inner = inner.enter();
+    tracing::info!(target : "spanned", "logged");
+    assert_eq!(*SPANNED.lock().unwrap(), ["outer::inner logged"]);
+}